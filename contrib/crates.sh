@@ -5,4 +5,4 @@
 # shellcheck disable=SC2034
 
 # Crates in this workspace to test.
-CRATES=(".")
+CRATES=("." "derive")
@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides `#[derive(ArbitraryOrd)]` for the [`ordered`] crate.
+//!
+//! Do not depend on this crate directly, enable the `derive` feature of `ordered` instead. The
+//! generated code refers to `::ordered` and will not compile without it.
+//!
+//! [`ordered`]: <https://docs.rs/ordered/>
+
+// Coding conventions.
+#![warn(missing_docs)]
+#![warn(deprecated_in_future)]
+
+use std::collections::BTreeMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DataEnum, DataStruct, DeriveInput, LitInt,
+    LitStr, Member, Path, Token, WherePredicate,
+};
+
+/// Derives `ArbitraryOrd` by comparing fields lexicographically in declaration order.
+///
+/// Enums are ordered first by variant, in declaration order, and then by the fields of the
+/// variant. Each field is compared using `ArbitraryOrd` if its type implements it and `Ord`
+/// otherwise.
+///
+/// # Attributes
+///
+/// On the type:
+///
+/// - `#[ordered(bound = "T: Ord")]`: Replaces the inferred `T: ArbitraryOrd` bounds on the type
+///   parameters with the given where clause predicates.
+///
+/// On fields:
+///
+/// - `#[ordered(skip)]`: Ignores the field when comparing. Values that differ only in skipped
+///   fields compare as `Equal`, so if the type also derives `PartialEq` the ordering is no longer
+///   consistent with it. Only skip fields that `PartialEq` ignores as well, for example by
+///   implementing `PartialEq` by hand.
+/// - `#[ordered(reverse)]`: Reverses the ordering of the field.
+/// - `#[ordered(by = path::to::fn)]`: Compares the field using a function with signature
+///   `fn(&F, &F) -> core::cmp::Ordering`.
+///
+/// On enum variants:
+///
+/// - `#[ordered(rank = N)]`: Gives the variant an explicit rank. Variants are ordered by rank and
+///   a variant without an explicit rank is ranked one higher than the variant before it, the same
+///   way enum discriminants are assigned. Ranks must be unique.
+#[proc_macro_derive(ArbitraryOrd, attributes(ordered))]
+pub fn derive_arbitrary_ord(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let container = ContainerAttrs::parse(&input.attrs)?;
+
+    let body = match input.data {
+        Data::Struct(ref data) => expand_struct(data)?,
+        Data::Enum(ref data) => expand_enum(data)?,
+        Data::Union(ref data) =>
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "`ArbitraryOrd` cannot be derived for unions",
+            )),
+    };
+
+    let mut generics = input.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        match container.bound {
+            Some(bound) => where_clause.predicates.extend(bound),
+            None =>
+                for param in input.generics.type_params() {
+                    let ident = &param.ident;
+                    where_clause.predicates.push(parse_quote!(#ident: ::ordered::ArbitraryOrd));
+                },
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::ordered::ArbitraryOrd for #name #ty_generics #where_clause {
+            fn arbitrary_cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                #[allow(unused_imports)]
+                use ::ordered::__private::{ViaArbitraryOrd as _, ViaOrd as _};
+                #body
+            }
+        }
+    })
+}
+
+fn expand_struct(data: &DataStruct) -> syn::Result<TokenStream2> {
+    let mut cmps = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let member = match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        cmps.push(attrs.compare(&quote!(&self.#member), &quote!(&other.#member)));
+    }
+    Ok(chain(&cmps))
+}
+
+fn expand_enum(data: &DataEnum) -> syn::Result<TokenStream2> {
+    if data.variants.is_empty() {
+        return Ok(quote!(match *self {}));
+    }
+
+    let mut ranks = BTreeMap::new();
+    let mut next_rank = Some(0_u64);
+    let mut rank_arms = Vec::new();
+    let mut field_arms = Vec::new();
+
+    for variant in &data.variants {
+        let attrs = VariantAttrs::parse(&variant.attrs)?;
+        let rank = match attrs.rank.or(next_rank) {
+            Some(rank) => rank,
+            None =>
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "implicit rank overflows `u64`, add an explicit `#[ordered(rank = N)]`",
+                )),
+        };
+        if let Some(previous) = ranks.insert(rank, &variant.ident) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("rank {} is already used by variant `{}`", rank, previous),
+            ));
+        }
+        next_rank = rank.checked_add(1);
+
+        let ident = &variant.ident;
+        rank_arms.push(quote!(Self::#ident { .. } => #rank));
+
+        let mut self_fields = Vec::new();
+        let mut other_fields = Vec::new();
+        let mut cmps = Vec::new();
+        for (index, field) in variant.fields.iter().enumerate() {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            let member = match field.ident {
+                Some(ref ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            if attrs.skip {
+                continue;
+            }
+            let this = format_ident!("__self_{}", index);
+            let that = format_ident!("__other_{}", index);
+            cmps.push(attrs.compare(&this.to_token_stream(), &that.to_token_stream()));
+            self_fields.push(quote!(#member: #this));
+            other_fields.push(quote!(#member: #that));
+        }
+        let body = chain(&cmps);
+        field_arms.push(quote! {
+            (Self::#ident { #(#self_fields,)* .. }, Self::#ident { #(#other_fields,)* .. }) => {
+                #body
+            }
+        });
+    }
+
+    // Ranks are unique so equal ranks imply equal variants.
+    let fallback =
+        if data.variants.len() > 1 { quote!(_ => ::core::cmp::Ordering::Equal,) } else { quote!() };
+
+    Ok(quote! {
+        let __self_rank: u64 = match self { #(#rank_arms,)* };
+        let __other_rank: u64 = match other { #(#rank_arms,)* };
+        match ::core::cmp::Ord::cmp(&__self_rank, &__other_rank) {
+            ::core::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
+        match (self, other) {
+            #(#field_arms)*
+            #fallback
+        }
+    })
+}
+
+/// Chains field comparisons, returning the first one that is not `Equal`.
+fn chain(cmps: &[TokenStream2]) -> TokenStream2 {
+    quote! {
+        #(
+            match #cmps {
+                ::core::cmp::Ordering::Equal => {}
+                ord => return ord,
+            }
+        )*
+        ::core::cmp::Ordering::Equal
+    }
+}
+
+/// Attributes set on the type being derived.
+#[derive(Default)]
+struct ContainerAttrs {
+    bound: Option<Punctuated<WherePredicate, Token![,]>>,
+}
+
+impl ContainerAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut ret = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("ordered")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bound") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    ret.bound = Some(lit.parse_with(Punctuated::parse_terminated)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `ordered` attribute on type, expected `bound`"))
+                }
+            })?;
+        }
+        Ok(ret)
+    }
+}
+
+/// Attributes set on a struct or enum variant field.
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    reverse: bool,
+    by: Option<Path>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut ret = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("ordered")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    ret.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("reverse") {
+                    ret.reverse = true;
+                    Ok(())
+                } else if meta.path.is_ident("by") {
+                    ret.by = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown `ordered` attribute on field, expected `skip`, `reverse` or `by`",
+                    ))
+                }
+            })?;
+        }
+        Ok(ret)
+    }
+
+    /// Returns an expression comparing `this` to `that`, both of which are references to a field.
+    fn compare(&self, this: &TokenStream2, that: &TokenStream2) -> TokenStream2 {
+        let cmp = if let Some(ref by) = self.by {
+            quote!(#by(#this, #that))
+        } else {
+            quote!((&::ordered::__private::Field(#this)).__ordered_cmp(#that))
+        };
+        if self.reverse {
+            quote!(::core::cmp::Ordering::reverse(#cmp))
+        } else {
+            cmp
+        }
+    }
+}
+
+/// Attributes set on an enum variant.
+#[derive(Default)]
+struct VariantAttrs {
+    rank: Option<u64>,
+}
+
+impl VariantAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut ret = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("ordered")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rank") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    ret.rank = Some(lit.base10_parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `ordered` attribute on variant, expected `rank`"))
+                }
+            })?;
+        }
+        Ok(ret)
+    }
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Implements `Arbitrary` for `Ordered` and `CheckedOrdered`.
+//!
+//! Both are generated exactly like `T`, so types containing `Ordered` or `CheckedOrdered` fields can
+//! derive `Arbitrary`.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{CheckedOrdered, Ordered};
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for Ordered<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> { T::arbitrary(u).map(Self) }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        T::arbitrary_take_rest(u).map(Self)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) { T::size_hint(depth) }
+}
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for CheckedOrdered<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> { T::arbitrary(u).map(Self) }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        T::arbitrary_take_rest(u).map(Self)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) { T::size_hint(depth) }
+}
@@ -0,0 +1,889 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Fixed-capacity sorted collections that never allocate, see [`ArrayMap`] and [`ArraySet`].
+//!
+//! The entries are stored inline in an array of capacity `N`, sorted by [`ArbitraryOrd`], so these
+//! types are usable in `no_std` environments without an allocator. Inserting into a full
+//! collection returns the rejected value instead of growing.
+//!
+//! # Examples
+//!
+//! ```
+//! use ordered::ArraySet;
+//!
+//! let mut set = ArraySet::<_, 2>::new();
+//! assert_eq!(set.insert("b"), Ok(true));
+//! assert_eq!(set.insert("a"), Ok(true));
+//! assert_eq!(set.insert("a"), Ok(false));
+//! assert_eq!(set.insert("c"), Err("c"));
+//!
+//! assert_eq!(set.as_slice(), ["a", "b"]);
+//! ```
+
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::mem::MaybeUninit;
+use core::ops::{Index, RangeBounds};
+use core::{fmt, ptr, slice};
+
+use crate::entries::{self, Store};
+pub use crate::entries::{
+    Entry, Iter, IterMut, Keys, OccupiedEntry, VacantEntry, Values, ValuesMut,
+};
+use crate::{ArbitraryOrd, Comparable};
+
+/// A vector with a fixed capacity of `N`, stored inline.
+struct ArrayVec<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    /// The number of initialized elements, at the start of `data`.
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    const fn new() -> Self {
+        // SAFETY: An array of `MaybeUninit` does not need to be initialized.
+        let data = unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() };
+        Self { data, len: 0 }
+    }
+
+    const fn is_full(&self) -> bool { self.len == N }
+
+    fn as_mut_ptr(&mut self) -> *mut T { self.data.as_mut_ptr().cast::<T>() }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: The element was initialized and is no longer reachable since `len` was reduced.
+        Some(unsafe { self.data[self.len].assume_init_read() })
+    }
+
+    /// Removes the elements for which `f` returns `false` in a single pass, moving each kept
+    /// element down over the gap left by the removed ones.
+    fn retain(&mut self, mut f: impl FnMut(&mut T) -> bool) {
+        /// Moves the unprocessed elements down and sets the length when dropped, which also
+        /// happens if `f` or the destructor of an element panics.
+        struct Guard<'a, T, const N: usize> {
+            vec: &'a mut ArrayVec<T, N>,
+            len: usize,
+            processed: usize,
+            removed: usize,
+        }
+
+        impl<T, const N: usize> Drop for Guard<'_, T, N> {
+            fn drop(&mut self) {
+                let base = self.vec.as_mut_ptr();
+                // SAFETY: The elements from `processed` to `len` are initialized and have not been
+                // moved, the `removed` slots before them are the gap left by removed elements.
+                unsafe {
+                    ptr::copy(
+                        base.add(self.processed),
+                        base.add(self.processed - self.removed),
+                        self.len - self.processed,
+                    );
+                }
+                self.vec.len = self.len - self.removed;
+            }
+        }
+
+        let len = self.len;
+        // Until the guard is dropped the elements are only reachable through it.
+        self.len = 0;
+        let mut guard = Guard { vec: self, len, processed: 0, removed: 0 };
+        while guard.processed < guard.len {
+            // SAFETY: `processed < len` so the element is initialized, and only the guard can reach
+            // it while `f` runs.
+            let element = unsafe { &mut *guard.vec.as_mut_ptr().add(guard.processed) };
+            let keep = f(element);
+            let element: *mut T = element;
+            guard.processed += 1;
+            if keep {
+                // SAFETY: The destination is in the gap left by removed elements.
+                unsafe { ptr::copy(element, element.sub(guard.removed), 1) };
+            } else {
+                guard.removed += 1;
+                // SAFETY: The element is initialized and is now part of the gap.
+                unsafe { ptr::drop_in_place(element) };
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        let elements: *mut [T] = self.as_mut_slice();
+        self.len = 0;
+        // SAFETY: The elements were initialized and are no longer reachable since `len` is zero.
+        unsafe { ptr::drop_in_place(elements) }
+    }
+}
+
+impl<T, const N: usize> Store<T> for ArrayVec<T, N> {
+    fn as_slice(&self) -> &[T] {
+        // SAFETY: The first `len` elements are initialized.
+        unsafe { slice::from_raw_parts(self.data.as_ptr().cast::<T>(), self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: The first `len` elements are initialized.
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len) }
+    }
+
+    fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len, "insertion index is out of bounds");
+        assert!(!self.is_full(), "ArrayVec is full");
+        // SAFETY: `index <= len < N` so both the shifted elements and `value` fit in the array.
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            ptr::write(p, value);
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "removal index is out of bounds");
+        self.len -= 1;
+        // SAFETY: `index` was less than `len` so the element is initialized, the elements after it
+        // are moved down to fill the gap.
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            let value = ptr::read(p);
+            ptr::copy(p.add(1), p, self.len - index);
+            value
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayVec<T, N> {
+    fn drop(&mut self) { self.clear() }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::new();
+        for element in self.as_slice() {
+            clone.insert(clone.len, element.clone());
+        }
+        clone
+    }
+}
+
+/// A sorted map with a fixed capacity of `N` entries, that never allocates.
+///
+/// The methods match those of `OrderedVecMap` where they can, with these differences:
+///
+/// - [`insert`] and [`entry`] return an error instead of growing when the map is full.
+/// - [`try_extend`] is the fallible bulk insertion, and collecting with [`FromIterator`] panics if
+///   the entries have more than `N` distinct keys.
+/// - There is no `into_vec` or `with_capacity`.
+///
+/// [`insert`]: ArrayMap::insert
+/// [`entry`]: ArrayMap::entry
+/// [`try_extend`]: ArrayMap::try_extend
+///
+/// # Examples
+///
+/// ```
+/// use ordered::ArrayMap;
+///
+/// let mut map = ArrayMap::<_, _, 2>::new();
+/// assert_eq!(map.insert(2, "b"), Ok(None));
+/// assert_eq!(map.insert(1, "a"), Ok(None));
+/// assert_eq!(map.insert(1, "c"), Ok(Some("a")));
+/// assert_eq!(map.insert(3, "d"), Err((3, "d")));
+///
+/// assert_eq!(map[&1], "c");
+/// assert!(map.is_full());
+/// ```
+pub struct ArrayMap<K, V, const N: usize> {
+    entries: ArrayVec<(K, V), N>,
+}
+
+impl<K, V, const N: usize> ArrayMap<K, V, N> {
+    /// Creates a new empty map.
+    #[must_use]
+    pub const fn new() -> Self { Self { entries: ArrayVec::new() } }
+
+    /// Returns the maximum number of entries the map can hold, `N`.
+    #[must_use]
+    pub const fn capacity(&self) -> usize { N }
+
+    /// Returns the number of entries in the map.
+    #[must_use]
+    pub const fn len(&self) -> usize { self.entries.len }
+
+    /// Returns `true` if the map contains no entries.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.entries.len == 0 }
+
+    /// Returns `true` if the map contains `N` entries, so only existing keys can be inserted.
+    #[must_use]
+    pub const fn is_full(&self) -> bool { self.entries.is_full() }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) { self.entries.clear() }
+
+    /// Returns the entries as a slice, sorted by key.
+    #[must_use]
+    pub fn as_slice(&self) -> &[(K, V)] { self.entries.as_slice() }
+
+    /// Returns the first entry, the one with the least key.
+    #[must_use]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.as_slice().first().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the last entry, the one with the greatest key.
+    #[must_use]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> { self.as_slice().last().map(|(k, v)| (k, v)) }
+
+    /// Removes and returns the first entry, moving the remaining entries to the front of the
+    /// array.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.entries.remove(0))
+        }
+    }
+
+    /// Removes and returns the last entry.
+    pub fn pop_last(&mut self) -> Option<(K, V)> { self.entries.pop() }
+
+    /// Retains only the entries for which `f` returns `true`.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        self.entries.retain(|(k, v)| f(k, v));
+    }
+
+    /// Returns an iterator over the entries, sorted by key.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, K, V> { Iter::new(self.entries.as_slice()) }
+
+    /// Returns an iterator over the entries with mutable values, sorted by key.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> { IterMut::new(self.entries.as_mut_slice()) }
+
+    /// Returns an iterator over the keys, in order.
+    #[must_use]
+    pub fn keys(&self) -> Keys<'_, K, V> { Keys::new(self.entries.as_slice()) }
+
+    /// Returns an iterator over the values, sorted by key.
+    #[must_use]
+    pub fn values(&self) -> Values<'_, K, V> { Values::new(self.entries.as_slice()) }
+
+    /// Returns an iterator over mutable values, sorted by key.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.entries.as_mut_slice())
+    }
+}
+
+impl<K: ArbitraryOrd, V, const N: usize> ArrayMap<K, V, N> {
+    /// Binary searches for `key`, returning its index or the index it would be inserted at.
+    fn search<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Result<usize, usize> {
+        entries::search(self.entries.as_slice(), entries::key, key)
+    }
+
+    /// Returns a reference to the value corresponding to `key`.
+    pub fn get<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Option<&V> {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair corresponding to `key`.
+    pub fn get_key_value<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Option<(&K, &V)> {
+        let (k, v) = &self.as_slice()[self.search(key).ok()?];
+        Some((k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to `key`.
+    pub fn get_mut<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries.as_mut_slice()[index].1)
+    }
+
+    /// Returns `true` if the map contains a value for `key`.
+    pub fn contains_key<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> bool {
+        self.search(key).is_ok()
+    }
+
+    /// Inserts a key-value pair, returning the old value if `key` was already present.
+    ///
+    /// Replacing the value of an existing key always succeeds, even when the map is full.
+    ///
+    /// # Errors
+    ///
+    /// If the map is full and does not contain `key`, returns the rejected key and value.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        match self.entry(key) {
+            Ok(Entry::Occupied(mut entry)) => Ok(Some(entry.insert(value))),
+            Ok(Entry::Vacant(entry)) => {
+                entry.insert(value);
+                Ok(None)
+            }
+            Err(key) => Err((key, value)),
+        }
+    }
+
+    /// Inserts each entry of `iter` in order, stopping at the first one that does not fit.
+    ///
+    /// # Errors
+    ///
+    /// If the map fills up, returns the first entry whose key was not present. The entries before
+    /// it have been inserted and the rest of `iter` is not consumed.
+    pub fn try_extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) -> Result<(), (K, V)> {
+        for (key, value) in iter {
+            self.insert(key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Removes `key` from the map, returning its value if it was present.
+    pub fn remove<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<V> {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes `key` from the map, returning the stored key and value if it was present.
+    pub fn remove_entry<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<(K, V)> {
+        let index = self.search(key).ok()?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Returns an iterator over the entries with keys within `range`, sorted by key.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        Q: Comparable<K> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Iter::new(entries::range(self.entries.as_slice(), entries::key, range))
+    }
+
+    /// Returns the entry for `key` for in-place manipulation.
+    ///
+    /// # Errors
+    ///
+    /// If the map is full and does not contain `key`, returns `key` since a vacant entry could
+    /// not be filled.
+    pub fn entry(&mut self, key: K) -> Result<Entry<'_, K, V>, K> {
+        match self.search(&key) {
+            Err(_) if self.is_full() => Err(key),
+            search => Ok(entries::entry(&mut self.entries, search, key)),
+        }
+    }
+}
+
+impl<K, V, const N: usize> Default for ArrayMap<K, V, N> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<K: Clone, V: Clone, const N: usize> Clone for ArrayMap<K, V, N> {
+    fn clone(&self) -> Self { Self { entries: self.entries.clone() } }
+}
+
+impl<K: PartialEq, V: PartialEq, const N: usize> PartialEq for ArrayMap<K, V, N> {
+    fn eq(&self, other: &Self) -> bool { self.as_slice() == other.as_slice() }
+}
+
+impl<K: Eq, V: Eq, const N: usize> Eq for ArrayMap<K, V, N> {}
+
+impl<K: Hash, V: Hash, const N: usize> Hash for ArrayMap<K, V, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.as_slice().hash(state) }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for ArrayMap<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: ArbitraryOrd, Q: Comparable<K> + ?Sized, V, const N: usize> Index<&Q>
+    for ArrayMap<K, V, N>
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to `key`.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is not present in the map.
+    fn index(&self, key: &Q) -> &V { self.get(key).expect("no entry found for key") }
+}
+
+impl<K: ArbitraryOrd, V, const N: usize> FromIterator<(K, V)> for ArrayMap<K, V, N> {
+    /// Inserts the entries in order.
+    ///
+    /// # Panics
+    ///
+    /// If the entries have more than `N` distinct keys, use [`ArrayMap::try_extend`] to handle
+    /// this without panicking.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        assert!(map.try_extend(iter).is_ok(), "more than {} distinct keys in ArrayMap", N);
+        map
+    }
+}
+
+impl<K, V, const N: usize> IntoIterator for ArrayMap<K, V, N> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<(K, V), N>;
+
+    fn into_iter(self) -> Self::IntoIter { IntoIter { vec: self.entries, front: 0 } }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a ArrayMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut ArrayMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// A sorted set with a fixed capacity of `N` elements, that never allocates.
+///
+/// [`insert`] returns the rejected element if the set is full. Lookups take any `Q` that is
+/// [`Comparable`] to `T`.
+///
+/// [`insert`]: ArraySet::insert
+pub struct ArraySet<T, const N: usize> {
+    elements: ArrayVec<T, N>,
+}
+
+impl<T, const N: usize> ArraySet<T, N> {
+    /// Creates a new empty set.
+    #[must_use]
+    pub const fn new() -> Self { Self { elements: ArrayVec::new() } }
+
+    /// Returns the maximum number of elements the set can hold, `N`.
+    #[must_use]
+    pub const fn capacity(&self) -> usize { N }
+
+    /// Returns the number of elements in the set.
+    #[must_use]
+    pub const fn len(&self) -> usize { self.elements.len }
+
+    /// Returns `true` if the set contains no elements.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.elements.len == 0 }
+
+    /// Returns `true` if the set contains `N` elements, so no new element can be inserted.
+    #[must_use]
+    pub const fn is_full(&self) -> bool { self.elements.is_full() }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) { self.elements.clear() }
+
+    /// Returns the elements as a slice, in order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] { self.elements.as_slice() }
+
+    /// Returns the least element.
+    #[must_use]
+    pub fn first(&self) -> Option<&T> { self.as_slice().first() }
+
+    /// Returns the greatest element.
+    #[must_use]
+    pub fn last(&self) -> Option<&T> { self.as_slice().last() }
+
+    /// Removes and returns the least element, moving the remaining elements to the front of the
+    /// array.
+    pub fn pop_first(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.elements.remove(0))
+        }
+    }
+
+    /// Removes and returns the greatest element.
+    pub fn pop_last(&mut self) -> Option<T> { self.elements.pop() }
+
+    /// Retains only the elements for which `f` returns `true`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) { self.elements.retain(|t| f(t)) }
+
+    /// Returns an iterator over the elements, in order.
+    pub fn iter(&self) -> slice::Iter<'_, T> { self.as_slice().iter() }
+}
+
+impl<T: ArbitraryOrd, const N: usize> ArraySet<T, N> {
+    /// Binary searches for `value`, returning its index or the index it would be inserted at.
+    fn search<Q: Comparable<T> + ?Sized>(&self, value: &Q) -> Result<usize, usize> {
+        entries::search(self.elements.as_slice(), entries::element, value)
+    }
+
+    /// Returns `true` if the set contains `value`.
+    pub fn contains<Q: Comparable<T> + ?Sized>(&self, value: &Q) -> bool {
+        self.search(value).is_ok()
+    }
+
+    /// Returns a reference to the stored element equal to `value`.
+    pub fn get<Q: Comparable<T> + ?Sized>(&self, value: &Q) -> Option<&T> {
+        let index = self.search(value).ok()?;
+        Some(&self.as_slice()[index])
+    }
+
+    /// Adds `value` to the set, returning `false` if it was already present.
+    ///
+    /// An element that is already present is not replaced, so this succeeds even when the set is
+    /// full.
+    ///
+    /// # Errors
+    ///
+    /// If the set is full and does not contain `value`, returns the rejected `value`.
+    pub fn insert(&mut self, value: T) -> Result<bool, T> {
+        match self.search(&value) {
+            Ok(_) => Ok(false),
+            Err(_) if self.is_full() => Err(value),
+            Err(index) => {
+                self.elements.insert(index, value);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Inserts each element of `iter` in order, stopping at the first one that does not fit.
+    ///
+    /// # Errors
+    ///
+    /// If the set fills up, returns the first element that was not present. The elements before
+    /// it have been inserted and the rest of `iter` is not consumed.
+    pub fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), T> {
+        for value in iter {
+            self.insert(value)?;
+        }
+        Ok(())
+    }
+
+    /// Removes `value` from the set, returning `true` if it was present.
+    pub fn remove<Q: Comparable<T> + ?Sized>(&mut self, value: &Q) -> bool {
+        self.take(value).is_some()
+    }
+
+    /// Removes and returns the stored element equal to `value`.
+    pub fn take<Q: Comparable<T> + ?Sized>(&mut self, value: &Q) -> Option<T> {
+        let index = self.search(value).ok()?;
+        Some(self.elements.remove(index))
+    }
+
+    /// Returns an iterator over the elements within `range`, in order.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    pub fn range<Q, R>(&self, range: R) -> slice::Iter<'_, T>
+    where
+        Q: Comparable<T> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        entries::range(self.elements.as_slice(), entries::element, range).iter()
+    }
+}
+
+impl<T, const N: usize> Default for ArraySet<T, N> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T: Clone, const N: usize> Clone for ArraySet<T, N> {
+    fn clone(&self) -> Self { Self { elements: self.elements.clone() } }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArraySet<T, N> {
+    fn eq(&self, other: &Self) -> bool { self.as_slice() == other.as_slice() }
+}
+
+impl<T: Eq, const N: usize> Eq for ArraySet<T, N> {}
+
+impl<T: Hash, const N: usize> Hash for ArraySet<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.as_slice().hash(state) }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArraySet<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ArbitraryOrd, const N: usize> FromIterator<T> for ArraySet<T, N> {
+    /// Inserts the elements in order.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `N` distinct elements, use [`ArraySet::try_extend`] to handle this
+    /// without panicking.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        assert!(set.try_extend(iter).is_ok(), "more than {} distinct elements in ArraySet", N);
+        set
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArraySet<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter { IntoIter { vec: self.elements, front: 0 } }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArraySet<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// An owning iterator over the entries of an [`ArrayMap`] or the elements of an [`ArraySet`].
+pub struct IntoIter<T, const N: usize> {
+    vec: ArrayVec<T, N>,
+    /// The index of the next element to yield from the front, elements before it have been moved
+    /// out.
+    front: usize,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    /// Returns the elements that have not been yielded yet.
+    fn remaining(&mut self) -> &mut [T] {
+        let front = self.front;
+        &mut self.vec.as_mut_slice()[front..]
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.vec.len {
+            return None;
+        }
+        self.front += 1;
+        // SAFETY: The element is initialized and is no longer reachable since `front` was advanced.
+        Some(unsafe { self.vec.data[self.front - 1].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.vec.len - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.vec.len {
+            return None;
+        }
+        self.vec.pop()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        let remaining: *mut [T] = self.remaining();
+        self.vec.len = 0;
+        // SAFETY: The remaining elements are initialized and are no longer reachable since `len`
+        // is zero, the elements before `front` have already been moved out.
+        unsafe { ptr::drop_in_place(remaining) }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&&self.vec.as_slice()[self.front..]).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    use core::ops::Bound;
+
+    use super::*;
+    use crate::entries::tests::{p, Point};
+
+    /// Counts how many times it is dropped.
+    #[derive(Debug)]
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) { self.0.set(self.0.get() + 1) }
+    }
+
+    fn map() -> ArrayMap<Point, u32, 4> {
+        [(p(3, 0), 0), (p(1, 1), 1), (p(2, 5), 2), (p(0, 9), 3)].into_iter().collect()
+    }
+
+    #[test]
+    fn map_insert_full() {
+        let mut map = map();
+
+        assert!(map.is_full());
+        assert_eq!(map.capacity(), 4);
+        assert_eq!(map.insert(p(9, 9), 9), Err((p(9, 9), 9)));
+        assert_eq!(map.insert(p(1, 1), 10), Ok(Some(1)));
+        assert!(map.keys().eq(&[p(0, 9), p(1, 1), p(2, 5), p(3, 0)]));
+
+        assert_eq!(map.remove(&p(1, 1)), Some(10));
+        assert_eq!(map.insert(p(9, 9), 9), Ok(None));
+        assert_eq!(map.last_key_value(), Some((&p(9, 9), &9)));
+    }
+
+    #[test]
+    fn map_try_extend() {
+        let mut map = ArrayMap::<_, _, 3>::new();
+        assert_eq!(map.try_extend([(2, 'a'), (1, 'b'), (2, 'c')]), Ok(()));
+        assert_eq!(map.as_slice(), [(1, 'b'), (2, 'c')]);
+
+        let mut rest = [(3, 'd'), (1, 'e'), (4, 'f'), (5, 'g')].into_iter();
+        assert_eq!(map.try_extend(&mut rest), Err((4, 'f')));
+        assert_eq!(map.as_slice(), [(1, 'e'), (2, 'c'), (3, 'd')]);
+        assert!(rest.eq([(5, 'g')]));
+    }
+
+    #[test]
+    #[should_panic(expected = "more than 2 distinct keys in ArrayMap")]
+    fn map_from_iter_panics_when_full() {
+        let _ = [(1, ()), (2, ()), (3, ())].into_iter().collect::<ArrayMap<_, _, 2>>();
+    }
+
+    #[test]
+    fn map_lookup() {
+        let mut map = map();
+
+        assert_eq!(map.get(&p(1, 1)), Some(&1));
+        assert_eq!(map.get(&p(1, 2)), None);
+        assert_eq!(map.get_key_value(&p(2, 5)), Some((&p(2, 5), &2)));
+        assert!(map.contains_key(&p(0, 9)));
+        assert_eq!(map[&p(3, 0)], 0);
+
+        *map.get_mut(&p(0, 9)).unwrap() += 10;
+        assert_eq!(map.remove_entry(&p(0, 9)), Some((p(0, 9), 13)));
+        assert_eq!(map.remove(&p(0, 9)), None);
+        assert_eq!(map.pop_first(), Some((p(1, 1), 1)));
+        assert_eq!(map.pop_last(), Some((p(3, 0), 0)));
+        assert_eq!(map.as_slice(), [(p(2, 5), 2)]);
+    }
+
+    #[test]
+    fn map_entry() {
+        let mut map = map();
+
+        *map.entry(p(1, 1)).unwrap().or_insert(0) += 10;
+        assert_eq!(map.entry(p(5, 5)).err(), Some(p(5, 5)));
+
+        map.remove(&p(3, 0));
+        *map.entry(p(5, 5)).unwrap().or_default() += 1;
+        assert!(map.values().eq(&[3, 11, 2, 1]));
+    }
+
+    #[test]
+    fn map_range_and_iteration() {
+        let mut map = map();
+
+        assert!(map.range(p(1, 0)..p(3, 0)).eq([(&p(1, 1), &1), (&p(2, 5), &2)]));
+        assert_eq!(map.range::<Point, _>((Bound::Excluded(&p(2, 5)), Bound::Unbounded)).len(), 1);
+
+        for (_, v) in &mut map {
+            *v *= 2;
+        }
+        map.values_mut().for_each(|v| *v += 1);
+        map.retain(|k, _| k.x != 2);
+
+        assert!(map.values().eq(&[7, 3, 1]));
+        assert_eq!(map.clone(), map);
+        assert!(map.into_iter().rev().eq([(p(3, 0), 1), (p(1, 1), 3), (p(0, 9), 7)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn map_range_reversed() { let _ = map().range(p(3, 0)..p(1, 0)); }
+
+    #[test]
+    fn set() {
+        let mut set = ArraySet::<_, 3>::new();
+        assert_eq!(set.insert(p(2, 0)), Ok(true));
+        assert_eq!(set.insert(p(0, 1)), Ok(true));
+        assert_eq!(set.insert(p(0, 1)), Ok(false));
+        assert_eq!(set.insert(p(1, 9)), Ok(true));
+        assert_eq!(set.insert(p(5, 5)), Err(p(5, 5)));
+        assert_eq!(set.insert(p(1, 9)), Ok(false));
+        assert_eq!(set.try_extend([p(0, 1), p(6, 6)]), Err(p(6, 6)));
+
+        assert!(set.contains(&p(2, 0)));
+        assert_eq!(set.get(&p(0, 1)), Some(&p(0, 1)));
+        assert_eq!(set.as_slice(), [p(0, 1), p(1, 9), p(2, 0)]);
+        assert!(set.range(p(1, 0)..).eq(&[p(1, 9), p(2, 0)]));
+        assert_eq!((set.first(), set.last()), (Some(&p(0, 1)), Some(&p(2, 0))));
+
+        assert!(set.remove(&p(2, 0)));
+        assert!(!set.remove(&p(2, 0)));
+        assert_eq!(set.take(&p(0, 1)), Some(p(0, 1)));
+        assert!(set.into_iter().eq([p(1, 9)]));
+
+        let set = [3, 1, 3, 2].into_iter().collect::<ArraySet<_, 3>>();
+        assert_eq!(set.as_slice(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn retain_keeps_order() {
+        let mut set = (0..8).collect::<ArraySet<u32, 8>>();
+        set.retain(|x| x % 3 != 0);
+        assert_eq!(set.as_slice(), [1, 2, 4, 5, 7]);
+        set.retain(|_| false);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn retain_panic_keeps_unvisited_elements() {
+        extern crate std;
+
+        let mut set = (0..6).collect::<ArraySet<u32, 8>>();
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            set.retain(|&x| {
+                assert!(x != 3, "stop");
+                x % 2 == 0
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(set.as_slice(), [0, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn drops_elements() {
+        let drops = Cell::new(0);
+        let mut map = ArrayMap::<u32, Counted, 4>::new();
+        for i in 0..4 {
+            assert!(map.insert(i, Counted(&drops)).is_ok());
+        }
+
+        drop(map.insert(4, Counted(&drops)));
+        assert_eq!(drops.get(), 1);
+        drop(map.insert(0, Counted(&drops)));
+        assert_eq!(drops.get(), 2);
+        map.retain(|k, _| *k != 1);
+        assert_eq!(drops.get(), 3);
+
+        let mut iter = map.into_iter();
+        drop(iter.next());
+        drop(iter.next_back());
+        assert_eq!(drops.get(), 5);
+        drop(iter);
+        assert_eq!(drops.get(), 6);
+
+        let mut map = ArrayMap::<u32, Counted, 2>::new();
+        assert!(map.insert(0, Counted(&drops)).is_ok());
+        map.clear();
+        assert_eq!(drops.get(), 7);
+        assert!(map.insert(1, Counted(&drops)).is_ok());
+        drop(map);
+        assert_eq!(drops.get(), 8);
+    }
+}
@@ -0,0 +1,533 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Extension traits for `BTreeMap` and `BTreeSet` keyed by [`Ordered<K>`].
+//!
+//! [`OrderedMapExt`] and [`OrderedSetExt`] provide methods that take `&K` and ranges of `K`
+//! directly, instead of requiring each key to be wrapped with [`Ordered::from_ref`]. Iterators
+//! returned by these methods yield `&K` instead of `&Ordered<K>`.
+//!
+//! The `arbitrary_find` methods look up keys by any query that is [`Comparable`] to `K`, such as
+//! `&str` for `String` keys. `BTreeMap` can only search for an `Ordered<K>`, so these scan the keys
+//! in order and take `O(n)` time.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use core::cmp::Ordering;
+//! use ordered::{ArbitraryOrd, Ordered, OrderedMapExt};
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! struct Point {
+//!     x: u32,
+//!     y: u32,
+//! }
+//!
+//! impl ArbitraryOrd for Point {
+//!     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+//!         (self.x, self.y).cmp(&(other.x, other.y))
+//!     }
+//! }
+//!
+//! let a = Point { x: 2, y: 3 };
+//! let b = Point { x: 1, y: 5 };
+//!
+//! let mut map = BTreeMap::new();
+//! map.arbitrary_insert(a, "a");
+//! map.arbitrary_insert(b, "b");
+//!
+//! assert_eq!(map.arbitrary_get(&a), Some(&"a"));
+//! assert_eq!(map.arbitrary_range(b..a).collect::<Vec<_>>(), [(&b, &"b")]);
+//! assert_eq!(map.arbitrary_keys().collect::<Vec<_>>(), [&b, &a]);
+//! ```
+
+use alloc::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+
+use crate::comparable::check_range;
+use crate::{ArbitraryOrd, Comparable, Ordered};
+
+/// Extension trait for `BTreeMap<Ordered<K>, V>` with methods that take unwrapped keys.
+///
+/// Each method is the same as the `BTreeMap` method without the `arbitrary_` prefix.
+pub trait OrderedMapExt<K: ArbitraryOrd + Eq, V> {
+    /// Returns a reference to the value corresponding to `key`.
+    fn arbitrary_get(&self, key: &K) -> Option<&V>;
+
+    /// Returns the key-value pair corresponding to `key`.
+    fn arbitrary_get_key_value(&self, key: &K) -> Option<(&K, &V)>;
+
+    /// Returns a mutable reference to the value corresponding to `key`.
+    fn arbitrary_get_mut(&mut self, key: &K) -> Option<&mut V>;
+
+    /// Returns `true` if the map contains a value for `key`.
+    fn arbitrary_contains_key(&self, key: &K) -> bool;
+
+    /// Inserts a key-value pair, returning the old value if `key` was already present.
+    fn arbitrary_insert(&mut self, key: K, value: V) -> Option<V>;
+
+    /// Removes `key` from the map, returning its value if it was present.
+    fn arbitrary_remove(&mut self, key: &K) -> Option<V>;
+
+    /// Removes `key` from the map, returning the stored key and value if it was present.
+    fn arbitrary_remove_entry(&mut self, key: &K) -> Option<(K, V)>;
+
+    /// Returns the entry for `key` for in-place manipulation.
+    fn arbitrary_entry(&mut self, key: K) -> btree_map::Entry<'_, Ordered<K>, V>;
+
+    /// Returns an iterator over the key-value pairs within `range`, in order.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    fn arbitrary_range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V>;
+
+    /// Returns an iterator over the key-value pairs, in order.
+    fn arbitrary_iter(&self) -> Iter<'_, K, V>;
+
+    /// Returns an iterator over the keys, in order.
+    fn arbitrary_keys(&self) -> Keys<'_, K, V>;
+
+    /// Returns the key-value pair whose key compares `Equal` to `key`.
+    ///
+    /// This scans the keys in order, stopping at the first key greater than `key`.
+    fn arbitrary_find<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Option<(&K, &V)>;
+
+    /// Returns the key and a mutable reference to the value whose key compares `Equal` to `key`.
+    ///
+    /// This scans the keys in order, stopping at the first key greater than `key`.
+    fn arbitrary_find_mut<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<(&K, &mut V)>;
+
+    /// Returns an iterator over the key-value pairs with keys within a `range` of queries, in
+    /// order.
+    ///
+    /// Finding the ends of the range scans the keys up to the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    fn arbitrary_find_range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        Q: Comparable<K> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>;
+}
+
+impl<K: ArbitraryOrd + Eq, V> OrderedMapExt<K, V> for BTreeMap<Ordered<K>, V> {
+    fn arbitrary_get(&self, key: &K) -> Option<&V> { self.get(Ordered::from_ref(key)) }
+
+    fn arbitrary_get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.get_key_value(Ordered::from_ref(key)).map(|(k, v)| (&k.0, v))
+    }
+
+    fn arbitrary_get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(Ordered::from_ref(key))
+    }
+
+    fn arbitrary_contains_key(&self, key: &K) -> bool { self.contains_key(Ordered::from_ref(key)) }
+
+    fn arbitrary_insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(Ordered(key), value)
+    }
+
+    fn arbitrary_remove(&mut self, key: &K) -> Option<V> { self.remove(Ordered::from_ref(key)) }
+
+    fn arbitrary_remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        self.remove_entry(Ordered::from_ref(key)).map(|(k, v)| (k.0, v))
+    }
+
+    fn arbitrary_entry(&mut self, key: K) -> btree_map::Entry<'_, Ordered<K>, V> {
+        self.entry(Ordered(key))
+    }
+
+    fn arbitrary_range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V> {
+        Range(self.range::<Ordered<K>, _>(bounds(&range)))
+    }
+
+    fn arbitrary_iter(&self) -> Iter<'_, K, V> { Iter(self.iter()) }
+
+    fn arbitrary_keys(&self) -> Keys<'_, K, V> { Keys(self.keys()) }
+
+    fn arbitrary_find<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Option<(&K, &V)> {
+        find(self.iter(), |(k, _)| key.compare(&k.0)).map(|(k, v)| (&k.0, v))
+    }
+
+    fn arbitrary_find_mut<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<(&K, &mut V)> {
+        find(self.iter_mut(), |(k, _)| key.compare(&k.0)).map(|(k, v)| (&k.0, v))
+    }
+
+    fn arbitrary_find_range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        Q: Comparable<K> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range(self.range::<Ordered<K>, _>(find_bounds(self.keys(), &range)))
+    }
+}
+
+/// Extension trait for `BTreeSet<Ordered<K>>` with methods that take unwrapped values.
+///
+/// Each method is the same as the `BTreeSet` method without the `arbitrary_` prefix.
+pub trait OrderedSetExt<K: ArbitraryOrd + Eq> {
+    /// Returns `true` if the set contains `value`.
+    fn arbitrary_contains(&self, value: &K) -> bool;
+
+    /// Returns a reference to the stored value equal to `value`.
+    fn arbitrary_get(&self, value: &K) -> Option<&K>;
+
+    /// Adds `value` to the set, returning `false` if it was already present.
+    fn arbitrary_insert(&mut self, value: K) -> bool;
+
+    /// Removes `value` from the set, returning `true` if it was present.
+    fn arbitrary_remove(&mut self, value: &K) -> bool;
+
+    /// Removes and returns the stored value equal to `value`.
+    fn arbitrary_take(&mut self, value: &K) -> Option<K>;
+
+    /// Returns an iterator over the values within `range`, in order.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    fn arbitrary_range<R: RangeBounds<K>>(&self, range: R) -> SetRange<'_, K>;
+
+    /// Returns an iterator over the values, in order.
+    fn arbitrary_iter(&self) -> SetIter<'_, K>;
+
+    /// Returns a reference to the stored value that compares `Equal` to `value`.
+    ///
+    /// This scans the values in order, stopping at the first value greater than `value`.
+    fn arbitrary_find<Q: Comparable<K> + ?Sized>(&self, value: &Q) -> Option<&K>;
+
+    /// Returns an iterator over the values within a `range` of queries, in order.
+    ///
+    /// Finding the ends of the range scans the values up to the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    fn arbitrary_find_range<Q, R>(&self, range: R) -> SetRange<'_, K>
+    where
+        Q: Comparable<K> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>;
+}
+
+impl<K: ArbitraryOrd + Eq> OrderedSetExt<K> for BTreeSet<Ordered<K>> {
+    fn arbitrary_contains(&self, value: &K) -> bool { self.contains(Ordered::from_ref(value)) }
+
+    fn arbitrary_get(&self, value: &K) -> Option<&K> {
+        self.get(Ordered::from_ref(value)).map(|v| &v.0)
+    }
+
+    fn arbitrary_insert(&mut self, value: K) -> bool { self.insert(Ordered(value)) }
+
+    fn arbitrary_remove(&mut self, value: &K) -> bool { self.remove(Ordered::from_ref(value)) }
+
+    fn arbitrary_take(&mut self, value: &K) -> Option<K> {
+        self.take(Ordered::from_ref(value)).map(|v| v.0)
+    }
+
+    fn arbitrary_range<R: RangeBounds<K>>(&self, range: R) -> SetRange<'_, K> {
+        SetRange(self.range::<Ordered<K>, _>(bounds(&range)))
+    }
+
+    fn arbitrary_iter(&self) -> SetIter<'_, K> { SetIter(self.iter()) }
+
+    fn arbitrary_find<Q: Comparable<K> + ?Sized>(&self, value: &Q) -> Option<&K> {
+        find(self.iter(), |v| value.compare(&v.0)).map(|v| &v.0)
+    }
+
+    fn arbitrary_find_range<Q, R>(&self, range: R) -> SetRange<'_, K>
+    where
+        Q: Comparable<K> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        SetRange(self.range::<Ordered<K>, _>(find_bounds(self.iter(), &range)))
+    }
+}
+
+/// Converts the bounds of `range` to bounds of `Ordered<K>`.
+fn bounds<K, R: RangeBounds<K>>(range: &R) -> (Bound<&Ordered<K>>, Bound<&Ordered<K>>) {
+    fn wrap<K>(bound: Bound<&K>) -> Bound<&Ordered<K>> {
+        match bound {
+            Bound::Included(k) => Bound::Included(Ordered::from_ref(k)),
+            Bound::Excluded(k) => Bound::Excluded(Ordered::from_ref(k)),
+            Bound::Unbounded => Bound::Unbounded,
+        }
+    }
+    (wrap(range.start_bound()), wrap(range.end_bound()))
+}
+
+/// Returns the first item that `cmp` returns `Equal` for, stopping at the first item it returns
+/// `Less` for.
+///
+/// `cmp` compares the query to an item, so items must be sorted in increasing order.
+fn find<I: Iterator>(items: I, cmp: impl Fn(&I::Item) -> Ordering) -> Option<I::Item> {
+    let mut items = items.skip_while(|item| cmp(item) == Ordering::Greater);
+    items.next().filter(|item| cmp(item) == Ordering::Equal)
+}
+
+/// Converts the bounds of a `range` of queries to bounds of the `keys` that they select.
+///
+/// Scans `keys`, which must be sorted, until the end of the range.
+///
+/// # Panics
+///
+/// If the start of the range is greater than the end, or if they are equal and both excluded.
+fn find_bounds<'a, K, Q, R>(
+    mut keys: impl Iterator<Item = &'a Ordered<K>>,
+    range: &R,
+) -> (Bound<&'a Ordered<K>>, Bound<&'a Ordered<K>>)
+where
+    K: 'a,
+    Q: Comparable<K> + ArbitraryOrd + ?Sized,
+    R: RangeBounds<Q>,
+{
+    let (start, end) = (range.start_bound(), range.end_bound());
+    check_range(start, end);
+
+    let before_start = |k: &Ordered<K>| match start {
+        Bound::Included(q) => q.compare(&k.0) == Ordering::Greater,
+        Bound::Excluded(q) => q.compare(&k.0) != Ordering::Less,
+        Bound::Unbounded => false,
+    };
+    let after_end = |k: &&Ordered<K>| match end {
+        Bound::Included(q) => q.compare(&k.0) == Ordering::Less,
+        Bound::Excluded(q) => q.compare(&k.0) != Ordering::Greater,
+        Bound::Unbounded => false,
+    };
+
+    let mut last = None;
+    let first = loop {
+        match keys.next() {
+            Some(k) if before_start(k) => last = Some(k),
+            Some(k) => break k,
+            // Every key is before the start, so select none of them.
+            None => return (last.map_or(Bound::Unbounded, Bound::Excluded), Bound::Unbounded),
+        }
+    };
+    let end = core::iter::once(first).chain(keys).find(after_end);
+    (Bound::Included(first), end.map_or(Bound::Unbounded, Bound::Excluded))
+}
+
+/// Implements the iterator traits for an iterator that wraps a `BTreeMap` or `BTreeSet` iterator.
+macro_rules! impl_iterator {
+    ($name:ident<$lt:lifetime, $($gen:ident),*>, $item:ty, |$x:ident| $unwrap:expr) => {
+        impl<$lt, $($gen),*> Iterator for $name<$lt, $($gen),*> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> { self.0.next().map(|$x| $unwrap) }
+
+            fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+        }
+
+        impl<$lt, $($gen),*> DoubleEndedIterator for $name<$lt, $($gen),*> {
+            fn next_back(&mut self) -> Option<Self::Item> { self.0.next_back().map(|$x| $unwrap) }
+        }
+
+        impl<$lt, $($gen),*> FusedIterator for $name<$lt, $($gen),*> {}
+    };
+}
+
+/// An iterator over the key-value pairs of a `BTreeMap<Ordered<K>, V>`.
+///
+/// Created by [`OrderedMapExt::arbitrary_iter`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V>(btree_map::Iter<'a, Ordered<K>, V>);
+
+impl_iterator!(Iter<'a, K, V>, (&'a K, &'a V), |kv| (&kv.0 .0, kv.1));
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// An iterator over the keys of a `BTreeMap<Ordered<K>, V>`.
+///
+/// Created by [`OrderedMapExt::arbitrary_keys`].
+#[derive(Debug, Clone)]
+pub struct Keys<'a, K, V>(btree_map::Keys<'a, Ordered<K>, V>);
+
+impl_iterator!(Keys<'a, K, V>, &'a K, |k| &k.0);
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+/// An iterator over a range of key-value pairs of a `BTreeMap<Ordered<K>, V>`.
+///
+/// Created by [`OrderedMapExt::arbitrary_range`].
+#[derive(Debug, Clone)]
+pub struct Range<'a, K, V>(btree_map::Range<'a, Ordered<K>, V>);
+
+impl_iterator!(Range<'a, K, V>, (&'a K, &'a V), |kv| (&kv.0 .0, kv.1));
+
+/// An iterator over the values of a `BTreeSet<Ordered<K>>`.
+///
+/// Created by [`OrderedSetExt::arbitrary_iter`].
+#[derive(Debug, Clone)]
+pub struct SetIter<'a, K>(btree_set::Iter<'a, Ordered<K>>);
+
+impl_iterator!(SetIter<'a, K>, &'a K, |k| &k.0);
+
+impl<'a, K> ExactSizeIterator for SetIter<'a, K> {}
+
+/// An iterator over a range of values of a `BTreeSet<Ordered<K>>`.
+///
+/// Created by [`OrderedSetExt::arbitrary_range`].
+#[derive(Debug, Clone)]
+pub struct SetRange<'a, K>(btree_set::Range<'a, Ordered<K>>);
+
+impl_iterator!(SetRange<'a, K>, &'a K, |k| &k.0);
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::cmp::Ordering;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    fn p(x: u32, y: u32) -> Point { Point { x, y } }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            (self.x, self.y).cmp(&(other.x, other.y))
+        }
+    }
+
+    /// Looks up points by their `x` coordinate only.
+    #[derive(PartialEq, Eq)]
+    struct X(u32);
+
+    impl Comparable<Point> for X {
+        fn compare(&self, key: &Point) -> Ordering { self.0.cmp(&key.x) }
+    }
+
+    impl ArbitraryOrd for X {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+    }
+
+    fn map() -> BTreeMap<Ordered<Point>, u32> {
+        let mut map = BTreeMap::new();
+        for (i, point) in (0..).zip([p(3, 0), p(1, 1), p(2, 5), p(0, 9)]) {
+            assert_eq!(map.arbitrary_insert(point, i), None);
+        }
+        map
+    }
+
+    #[test]
+    fn map_lookup() {
+        let mut map = map();
+
+        assert_eq!(map.arbitrary_get(&p(1, 1)), Some(&1));
+        assert_eq!(map.arbitrary_get(&p(1, 2)), None);
+        assert_eq!(map.arbitrary_get_key_value(&p(2, 5)), Some((&p(2, 5), &2)));
+        assert!(map.arbitrary_contains_key(&p(0, 9)));
+
+        *map.arbitrary_get_mut(&p(0, 9)).unwrap() += 10;
+        assert_eq!(map.arbitrary_insert(p(0, 9), 4), Some(13));
+        *map.arbitrary_entry(p(7, 7)).or_insert(0) += 1;
+        assert_eq!(map.arbitrary_get(&p(7, 7)), Some(&1));
+
+        assert_eq!(map.arbitrary_remove(&p(3, 0)), Some(0));
+        assert_eq!(map.arbitrary_remove(&p(3, 0)), None);
+        assert_eq!(map.arbitrary_remove_entry(&p(1, 1)), Some((p(1, 1), 1)));
+    }
+
+    #[test]
+    fn map_iteration() {
+        let map = map();
+
+        let keys = map.arbitrary_keys().copied().collect::<Vec<_>>();
+        assert_eq!(keys, [p(0, 9), p(1, 1), p(2, 5), p(3, 0)]);
+        assert_eq!(map.arbitrary_iter().len(), 4);
+        assert_eq!(map.arbitrary_iter().next_back(), Some((&p(3, 0), &0)));
+
+        let range = map.arbitrary_range(p(1, 0)..p(3, 0)).collect::<Vec<_>>();
+        assert_eq!(range, [(&p(1, 1), &1), (&p(2, 5), &2)]);
+        assert_eq!(map.arbitrary_range(p(1, 1)..=p(3, 0)).count(), 3);
+        assert_eq!(map.arbitrary_range(..&p(1, 1)).count(), 1);
+        assert_eq!(map.arbitrary_range((Bound::Excluded(&p(2, 5)), Bound::Unbounded)).count(), 1);
+    }
+
+    #[test]
+    fn set() {
+        let mut set = BTreeSet::new();
+        assert!(set.arbitrary_insert(p(2, 0)));
+        assert!(set.arbitrary_insert(p(0, 1)));
+        assert!(set.arbitrary_insert(p(1, 9)));
+        assert!(!set.arbitrary_insert(p(1, 9)));
+
+        assert!(set.arbitrary_contains(&p(2, 0)));
+        assert_eq!(set.arbitrary_get(&p(0, 1)), Some(&p(0, 1)));
+        assert_eq!(set.arbitrary_iter().collect::<Vec<_>>(), [&p(0, 1), &p(1, 9), &p(2, 0)]);
+        assert_eq!(set.arbitrary_range(p(1, 0)..).collect::<Vec<_>>(), [&p(1, 9), &p(2, 0)]);
+
+        assert!(set.arbitrary_remove(&p(2, 0)));
+        assert!(!set.arbitrary_remove(&p(2, 0)));
+        assert_eq!(set.arbitrary_take(&p(0, 1)), Some(p(0, 1)));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.arbitrary_find(&X(1)), Some(&p(1, 9)));
+        assert_eq!(set.arbitrary_find_range(..=X(1)).count(), 1);
+    }
+
+    #[test]
+    fn map_find() {
+        let mut map = map();
+
+        assert_eq!(map.arbitrary_find(&X(2)), Some((&p(2, 5), &2)));
+        assert_eq!(map.arbitrary_find(&X(5)), None);
+        assert_eq!(map.arbitrary_find(&p(1, 1)), Some((&p(1, 1), &1)));
+        assert_eq!(map.arbitrary_find(&p(1, 2)), None);
+
+        *map.arbitrary_find_mut(&X(0)).unwrap().1 += 10;
+        assert_eq!(map.arbitrary_get(&p(0, 9)), Some(&13));
+
+        let range = map.arbitrary_find_range(X(1)..X(3)).collect::<Vec<_>>();
+        assert_eq!(range, [(&p(1, 1), &1), (&p(2, 5), &2)]);
+        assert_eq!(map.arbitrary_find_range(X(1)..=X(3)).count(), 3);
+        assert_eq!(map.arbitrary_find_range((Bound::Excluded(X(0)), Bound::Unbounded)).count(), 3);
+        assert_eq!(map.arbitrary_find_range(..X(0)).count(), 0);
+        assert_eq!(map.arbitrary_find_range(X(4)..).count(), 0);
+        assert_eq!(map.arbitrary_find_range(X(2)..X(2)).count(), 0);
+        assert_eq!(
+            BTreeMap::<Ordered<Point>, u32>::new().arbitrary_find_range::<X, _>(..).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn find_unsized() {
+        let mut map = BTreeMap::new();
+        map.arbitrary_insert(String::from("b"), 1);
+        map.arbitrary_insert(String::from("a"), 0);
+
+        assert_eq!(map.arbitrary_find("b"), Some((&String::from("b"), &1)));
+        assert_eq!(map.arbitrary_find("c"), None);
+        assert_eq!(
+            map.arbitrary_find_range::<str, _>((Bound::Included("a"), Bound::Excluded("b")))
+                .count(),
+            1
+        );
+
+        let set = map.into_keys().collect::<BTreeSet<_>>();
+        assert_eq!(set.arbitrary_find("a"), Some(&String::from("a")));
+        assert_eq!(
+            set.arbitrary_find_range::<str, _>((Bound::Included("aa"), Bound::Unbounded)).count(),
+            1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn reversed_range() { map().arbitrary_find_range(p(2, 0)..p(1, 0)); }
+
+    #[test]
+    #[should_panic(expected = "range start and end are equal and excluded")]
+    fn empty_excluded_range() {
+        map().arbitrary_find_range((Bound::Excluded(p(1, 0)), Bound::Excluded(p(1, 0))));
+    }
+}
@@ -0,0 +1,600 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides [`CheckedOrdered`], an [`Ordered`] that checks `ArbitraryOrd` impls at
+//! runtime in debug builds.
+//!
+//! On every comparison `CheckedOrdered` checks that `arbitrary_cmp` agrees with `PartialEq` and that
+//! swapping the arguments reverses the result. By default a violation panics, a different hook can
+//! be installed with [`set_hook`]. In release builds (without `debug_assertions`) no checks are done
+//! and `CheckedOrdered<T>` behaves exactly like `Ordered<T>`.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{self, AtomicPtr};
+use core::{fmt, ptr};
+
+use crate::{ArbitraryOrd, Ordered};
+
+/// A wrapper type that implements `PartialOrd` and `Ord`, checking the `ArbitraryOrd` impl of `T`
+/// in debug builds.
+///
+/// Apart from the checks this is the same as [`Ordered`], it has the same trait impls and
+/// projections. `T` does not need to implement `Debug`, a [`Violation`] only includes the values
+/// that were compared when they are compared with [`CheckedOrdered::debug_cmp`].
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{ArbitraryOrd, CheckedOrdered};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// impl ArbitraryOrd for Point {
+///     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+///         // Bug: `y` is ignored, so different points can compare `Equal`.
+///         self.x.cmp(&other.x)
+///     }
+/// }
+///
+/// let a = CheckedOrdered(Point { x: 1, y: 2 });
+/// let b = CheckedOrdered(Point { x: 1, y: 3 });
+///
+/// // In debug builds this panics because `a` and `b` compare `Equal` but `a != b`.
+/// let _ = std::panic::catch_unwind(|| a < b);
+/// ```
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct CheckedOrdered<T: ?Sized>(pub T);
+
+impl<T: Copy> Copy for CheckedOrdered<T> {}
+
+impl<T> CheckedOrdered<T> {
+    /// Creates a new wrapped ordered type.
+    ///
+    /// The inner type is public so this function is never explicitly needed.
+    pub const fn new(inner: T) -> Self { Self(inner) }
+
+    /// Converts into an `Ordered<T>`, which does not do any checks.
+    pub fn into_ordered(self) -> Ordered<T> { Ordered(self.0) }
+
+    /// Creates an `&[CheckedOrdered<T>]` from a slice without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_slice(slice: &[T]) -> &[Self] {
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so `[CheckedOrdered<T>]` has the same
+        // layout as `[T]`.
+        unsafe { &*(slice as *const [T] as *const [Self]) }
+    }
+
+    /// Creates an `&mut [CheckedOrdered<T>]` from a mutable slice without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_mut_slice(slice: &mut [T]) -> &mut [Self] {
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so `[CheckedOrdered<T>]` has the same
+        // layout as `[T]`.
+        unsafe { &mut *(slice as *mut [T] as *mut [Self]) }
+    }
+
+    /// Returns the inner slice of an `&[CheckedOrdered<T>]` without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn inner_slice(slice: &[Self]) -> &[T] {
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so `[CheckedOrdered<T>]` has the same
+        // layout as `[T]`.
+        unsafe { &*(slice as *const [Self] as *const [T]) }
+    }
+
+    /// Returns the inner slice of an `&mut [CheckedOrdered<T>]` without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn inner_mut_slice(slice: &mut [Self]) -> &mut [T] {
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so `[CheckedOrdered<T>]` has the same
+        // layout as `[T]`.
+        unsafe { &mut *(slice as *mut [Self] as *mut [T]) }
+    }
+
+    /// Creates a `Vec<CheckedOrdered<T>>` from a vector without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn from_vec(vec: Vec<T>) -> Vec<Self> {
+        let mut vec = ManuallyDrop::new(vec);
+        let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+        // SAFETY: `CheckedOrdered<T>` has the same size and alignment as `T` and the original
+        // vector is never dropped.
+        unsafe { Vec::from_raw_parts(ptr.cast::<Self>(), len, cap) }
+    }
+
+    /// Returns the inner vector of a `Vec<CheckedOrdered<T>>` without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_vec(vec: Vec<Self>) -> Vec<T> {
+        let mut vec = ManuallyDrop::new(vec);
+        let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+        // SAFETY: `CheckedOrdered<T>` has the same size and alignment as `T` and the original
+        // vector is never dropped.
+        unsafe { Vec::from_raw_parts(ptr.cast::<T>(), len, cap) }
+    }
+
+    /// Creates an array of `CheckedOrdered<T>` from an array without converting each element.
+    pub fn from_array<const N: usize>(array: [T; N]) -> [Self; N] {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so `[CheckedOrdered<T>; N]` has the
+        // same layout as `[T; N]`, and the original array is never dropped.
+        unsafe { ptr::read(ptr::addr_of!(*array).cast::<[Self; N]>()) }
+    }
+
+    /// Returns the inner array of an array of `CheckedOrdered<T>` without converting each element.
+    pub fn into_array<const N: usize>(array: [Self; N]) -> [T; N] {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so `[T; N]` has the same layout as
+        // `[CheckedOrdered<T>; N]`, and the original array is never dropped.
+        unsafe { ptr::read(ptr::addr_of!(*array).cast::<[T; N]>()) }
+    }
+}
+
+impl<T: ?Sized> CheckedOrdered<T> {
+    /// Creates a `CheckedOrdered<T>` from a reference.
+    ///
+    /// This allows: `let found = map.get(CheckedOrdered::from_ref(&a));`
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: `CheckedOrdered` is `repr(transparent)` over `T`.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Creates a `CheckedOrdered<T>` from a mutable reference.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_mut(value: &mut T) -> &mut Self {
+        // SAFETY: `CheckedOrdered` is `repr(transparent)` over `T`.
+        unsafe { &mut *(value as *mut T as *mut Self) }
+    }
+
+    /// Creates a `Box<CheckedOrdered<T>>` from a box without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::ptr_as_ptr)]
+    #[must_use]
+    pub fn from_box(boxed: Box<T>) -> Box<Self> {
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so the layout and pointer metadata
+        // are the same as `T`.
+        unsafe { Box::from_raw(Box::into_raw(boxed) as *mut Self) }
+    }
+
+    /// Returns the inner box of a `Box<CheckedOrdered<T>>` without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::ptr_as_ptr)]
+    #[must_use]
+    pub fn into_box(boxed: Box<Self>) -> Box<T> {
+        // SAFETY: `CheckedOrdered<T>` is `repr(transparent)` so the layout and pointer metadata
+        // are the same as `T`.
+        unsafe { Box::from_raw(Box::into_raw(boxed) as *mut T) }
+    }
+}
+
+impl<T: ArbitraryOrd + fmt::Debug + ?Sized> CheckedOrdered<T> {
+    /// Compares `self` to `other` the same as [`Ord::cmp`], but a [`Violation`] also includes the
+    /// two values.
+    #[track_caller]
+    pub fn debug_cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.0, &other.0);
+        checked_cmp(a, b, Some((&a, &b)))
+    }
+}
+
+/// Compares `a` to `b`, checking the result in debug builds.
+#[track_caller]
+fn checked_cmp<T, U>(a: &T, b: &U, operands: Option<(&dyn fmt::Debug, &dyn fmt::Debug)>) -> Ordering
+where
+    T: ArbitraryOrd<U> + ?Sized,
+    U: ArbitraryOrd<T> + ?Sized,
+{
+    let ord = a.arbitrary_cmp(b);
+    if cfg!(debug_assertions) {
+        let reversed = b.arbitrary_cmp(a);
+        if reversed != ord.reverse() {
+            let kind = ViolationKind::NotReversed { reversed };
+            report(&Violation::new::<T>(kind, operands, ord));
+        }
+        let eq = a == b;
+        if (ord == Ordering::Equal) != eq {
+            let kind = ViolationKind::InconsistentWithEq { eq };
+            report(&Violation::new::<T>(kind, operands, ord));
+        }
+    }
+    ord
+}
+
+// The comparison operators are overridden so that `#[track_caller]` reports the caller's location
+// instead of the default methods in `core`. Unlike `Ordered`, comparing against a different type
+// `U` also requires `U: ArbitraryOrd<T>` so that swapping the arguments can be checked.
+
+impl<T, U> PartialOrd<CheckedOrdered<U>> for CheckedOrdered<T>
+where
+    T: ArbitraryOrd<U> + ?Sized,
+    U: ArbitraryOrd<T> + ?Sized,
+{
+    #[track_caller]
+    fn partial_cmp(&self, other: &CheckedOrdered<U>) -> Option<Ordering> {
+        Some(checked_cmp(&self.0, &other.0, None))
+    }
+
+    #[track_caller]
+    fn lt(&self, other: &CheckedOrdered<U>) -> bool {
+        checked_cmp(&self.0, &other.0, None) == Ordering::Less
+    }
+
+    #[track_caller]
+    fn le(&self, other: &CheckedOrdered<U>) -> bool {
+        checked_cmp(&self.0, &other.0, None) != Ordering::Greater
+    }
+
+    #[track_caller]
+    fn gt(&self, other: &CheckedOrdered<U>) -> bool {
+        checked_cmp(&self.0, &other.0, None) == Ordering::Greater
+    }
+
+    #[track_caller]
+    fn ge(&self, other: &CheckedOrdered<U>) -> bool {
+        checked_cmp(&self.0, &other.0, None) != Ordering::Less
+    }
+}
+
+impl<T: ArbitraryOrd + Eq + ?Sized> Ord for CheckedOrdered<T> {
+    #[track_caller]
+    fn cmp(&self, other: &Self) -> Ordering { checked_cmp(&self.0, &other.0, None) }
+}
+
+impl<T, U> PartialEq<CheckedOrdered<U>> for CheckedOrdered<T>
+where
+    T: ArbitraryOrd<U> + ?Sized,
+    U: ArbitraryOrd<T> + ?Sized,
+{
+    #[track_caller]
+    fn eq(&self, other: &CheckedOrdered<U>) -> bool {
+        if cfg!(debug_assertions) {
+            checked_cmp(&self.0, &other.0, None);
+        }
+        self.0 == other.0
+    }
+}
+
+impl<T: ArbitraryOrd + Eq + ?Sized> Eq for CheckedOrdered<T> {}
+
+// Comparisons against a bare `T`. These can not overlap with the impls above because
+// `CheckedOrdered<U>` never implements `ArbitraryOrd`.
+
+impl<T: ArbitraryOrd + ?Sized> PartialEq<T> for CheckedOrdered<T> {
+    #[track_caller]
+    fn eq(&self, other: &T) -> bool {
+        if cfg!(debug_assertions) {
+            checked_cmp(&self.0, other, None);
+        }
+        self.0 == *other
+    }
+}
+
+impl<T: ArbitraryOrd + ?Sized> PartialOrd<T> for CheckedOrdered<T> {
+    #[track_caller]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> { Some(checked_cmp(&self.0, other, None)) }
+}
+
+impl<T: Hash + ?Sized> Hash for CheckedOrdered<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.0.hash(state) }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for CheckedOrdered<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.0, f) }
+}
+
+impl<T> From<T> for CheckedOrdered<T> {
+    fn from(inner: T) -> Self { Self(inner) }
+}
+
+impl<T> From<Ordered<T>> for CheckedOrdered<T> {
+    fn from(ordered: Ordered<T>) -> Self { Self(ordered.0) }
+}
+
+impl<T> From<CheckedOrdered<T>> for Ordered<T> {
+    fn from(checked: CheckedOrdered<T>) -> Self { Self(checked.0) }
+}
+
+impl<T: ?Sized> AsRef<T> for CheckedOrdered<T> {
+    fn as_ref(&self) -> &T { &self.0 }
+}
+
+impl<T: ?Sized> AsMut<T> for CheckedOrdered<T> {
+    fn as_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+impl<T: ?Sized> Borrow<T> for CheckedOrdered<T> {
+    fn borrow(&self) -> &T { &self.0 }
+}
+
+impl<T: ?Sized> BorrowMut<T> for CheckedOrdered<T> {
+    fn borrow_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+impl<T: ?Sized> Deref for CheckedOrdered<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T: ?Sized> DerefMut for CheckedOrdered<T> {
+    fn deref_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+/// An `ArbitraryOrd` impl that was caught misbehaving by [`CheckedOrdered`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct Violation<'a> {
+    /// What went wrong.
+    pub kind: ViolationKind,
+    /// The name of the type whose `ArbitraryOrd` impl is wrong.
+    pub type_name: &'static str,
+    /// The left operand, `a` in `a.arbitrary_cmp(b)`.
+    ///
+    /// Only set when comparing with [`CheckedOrdered::debug_cmp`], the trait impls do not require
+    /// `T: Debug`.
+    pub a: Option<&'a dyn fmt::Debug>,
+    /// The right operand, `b` in `a.arbitrary_cmp(b)`, set together with `a`.
+    pub b: Option<&'a dyn fmt::Debug>,
+    /// The result of `a.arbitrary_cmp(b)`.
+    pub ordering: Ordering,
+    /// Where the comparison was done, the caller of the `PartialOrd`, `Ord` or `PartialEq` method.
+    ///
+    /// Comparisons done inside the standard library, for example by `sort` or `BTreeMap`, report
+    /// a location in the standard library.
+    pub location: &'static Location<'static>,
+}
+
+impl<'a> Violation<'a> {
+    #[track_caller]
+    fn new<T: ?Sized>(
+        kind: ViolationKind,
+        operands: Option<(&'a dyn fmt::Debug, &'a dyn fmt::Debug)>,
+        ordering: Ordering,
+    ) -> Self {
+        Self {
+            kind,
+            type_name: core::any::type_name::<T>(),
+            a: operands.map(|(a, _)| a),
+            b: operands.map(|(_, b)| b),
+            ordering,
+            location: Location::caller(),
+        }
+    }
+}
+
+impl fmt::Display for Violation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`arbitrary_cmp` for `{}` returned {:?} ", self.type_name, self.ordering)?;
+        if let (Some(a), Some(b)) = (self.a, self.b) {
+            write!(f, "for a = {:?} and b = {:?} ", a, b)?;
+        }
+        match self.kind {
+            ViolationKind::NotReversed { reversed } => write!(
+                f,
+                "but returned {:?} with the arguments swapped, expected {:?}",
+                reversed,
+                self.ordering.reverse()
+            )?,
+            ViolationKind::InconsistentWithEq { eq } => write!(f, "but `==` returned {}", eq)?,
+        }
+        write!(f, " at {}", self.location)
+    }
+}
+
+/// The kind of [`Violation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// Swapping the arguments to `arbitrary_cmp` did not reverse the result.
+    NotReversed {
+        /// The result of `b.arbitrary_cmp(a)`.
+        reversed: Ordering,
+    },
+    /// `arbitrary_cmp` returned `Equal` but `a != b`, or `a == b` but not `Equal`.
+    InconsistentWithEq {
+        /// The result of `a == b`.
+        eq: bool,
+    },
+}
+
+#[cfg(target_has_atomic = "ptr")]
+static HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Installs a hook that is called, instead of panicking, when [`CheckedOrdered`] detects a
+/// [`Violation`].
+///
+/// The hook is global. If it returns, the comparison returns the result of `arbitrary_cmp`.
+#[cfg(target_has_atomic = "ptr")]
+pub fn set_hook(hook: fn(&Violation)) { HOOK.store(hook as *mut (), atomic::Ordering::Release) }
+
+/// Removes the hook installed with [`set_hook`], violations panic again.
+#[cfg(target_has_atomic = "ptr")]
+pub fn reset_hook() { HOOK.store(core::ptr::null_mut(), atomic::Ordering::Release) }
+
+/// Calls the installed hook or panics.
+#[track_caller]
+fn report(violation: &Violation) {
+    #[cfg(target_has_atomic = "ptr")]
+    {
+        let hook = HOOK.load(atomic::Ordering::Acquire);
+        if !hook.is_null() {
+            // SAFETY: The only non-null values stored in `HOOK` are `fn(&Violation)` pointers.
+            let hook = unsafe { core::mem::transmute::<*mut (), fn(&Violation)>(hook) };
+            return hook(violation);
+        }
+    }
+    panic!("{}", violation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            (self.x, self.y).cmp(&(other.x, other.y))
+        }
+    }
+
+    /// Ignores `y`, so is inconsistent with `PartialEq`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct IgnoresY(Point);
+
+    impl ArbitraryOrd for IgnoresY {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.x.cmp(&other.0.x) }
+    }
+
+    /// Never returns `Greater`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct NeverGreater(u32);
+
+    impl ArbitraryOrd for NeverGreater {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            if self == other {
+                Ordering::Equal
+            } else {
+                Ordering::Less
+            }
+        }
+    }
+
+    /// Does not implement `Debug`.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    struct Opaque(u32);
+
+    impl ArbitraryOrd for Opaque {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+    }
+
+    #[test]
+    fn correct_impl() {
+        let a = CheckedOrdered(Point { x: 1, y: 2 });
+        let b = CheckedOrdered(Point { x: 1, y: 3 });
+
+        assert!(a < b);
+        assert!(a == a);
+        assert_eq!(b.cmp(&a), Ordering::Greater);
+        assert_eq!(a.debug_cmp(&b), Ordering::Less);
+        assert!(CheckedOrdered::from_ref(&a.0) < &b);
+    }
+
+    #[test]
+    fn does_not_require_debug() {
+        let a = CheckedOrdered(Opaque(1));
+        let b = CheckedOrdered(Opaque(2));
+
+        assert!(a < b);
+        assert!(a != b);
+        assert_eq!(a.cmp(&b), Ordering::Less);
+    }
+
+    #[test]
+    fn compares_with_bare_and_other_types() {
+        let a = CheckedOrdered(Point { x: 1, y: 2 });
+
+        assert!(a == Point { x: 1, y: 2 });
+        assert!(a < Point { x: 2, y: 0 });
+
+        #[cfg(feature = "alloc")]
+        {
+            use alloc::string::String;
+
+            let s = CheckedOrdered(String::from("abc"));
+            assert!(s == *CheckedOrdered::from_ref("abc"));
+            assert!(s < *CheckedOrdered::from_ref("abd"));
+        }
+    }
+
+    #[test]
+    fn projections() {
+        let mut points = [Point { x: 2, y: 0 }, Point { x: 1, y: 5 }];
+        CheckedOrdered::from_mut_slice(&mut points).sort_unstable();
+        assert_eq!(points, [Point { x: 1, y: 5 }, Point { x: 2, y: 0 }]);
+
+        let checked = CheckedOrdered::from_array(points);
+        assert!(checked[0] < checked[1]);
+        assert_eq!(CheckedOrdered::inner_slice(&checked), &points);
+        assert_eq!(CheckedOrdered::into_array(checked), points);
+        assert!(CheckedOrdered::from_slice(&points)[0] < CheckedOrdered::from_slice(&points)[1]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn alloc_projections() {
+        use alloc::vec;
+
+        let checked = CheckedOrdered::from_vec(vec![Point { x: 1, y: 2 }]);
+        assert_eq!(CheckedOrdered::into_vec(checked), [Point { x: 1, y: 2 }]);
+
+        let boxed: Box<[u32]> = vec![1, 2].into_boxed_slice();
+        let boxed = CheckedOrdered::from_box(boxed);
+        assert!(*boxed < *CheckedOrdered::from_ref(&[1, 3][..]));
+        assert_eq!(&*CheckedOrdered::into_box(boxed), &[1, 2]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "for `ordered::checked::tests::IgnoresY` returned Equal but `==` returned false"
+    )]
+    fn inconsistent_with_eq_panics() {
+        let a = CheckedOrdered(IgnoresY(Point { x: 1, y: 2 }));
+        let b = CheckedOrdered(IgnoresY(Point { x: 1, y: 3 }));
+
+        let _ = a.cmp(&b);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "for a = IgnoresY(Point { x: 1, y: 2 }) and b = IgnoresY(Point { x: 1, y: 3 }) but `==` returned false"
+    )]
+    fn debug_cmp_shows_operands() {
+        let a = CheckedOrdered(IgnoresY(Point { x: 1, y: 2 }));
+        let b = CheckedOrdered(IgnoresY(Point { x: 1, y: 3 }));
+
+        let _ = a.debug_cmp(&b);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "returned Less with the arguments swapped, expected Greater")]
+    fn not_reversed_panics() {
+        let _ = CheckedOrdered(NeverGreater(1)) < CheckedOrdered(NeverGreater(2));
+    }
+
+    #[test]
+    fn violation_message() {
+        extern crate std;
+        use std::string::ToString;
+
+        let kind = ViolationKind::InconsistentWithEq { eq: true };
+        let violation = Violation::new::<u32>(kind, Some((&1, &2)), Ordering::Less);
+        assert!(violation.to_string().starts_with(
+            "`arbitrary_cmp` for `u32` returned Less for a = 1 and b = 2 but `==` returned true at "
+        ));
+
+        let violation = Violation::new::<u32>(kind, None, Ordering::Less);
+        assert!(violation
+            .to_string()
+            .starts_with("`arbitrary_cmp` for `u32` returned Less but `==` returned true at "));
+    }
+}
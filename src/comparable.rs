@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides [`Comparable`], for looking up keys using a different type.
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::ops::Bound;
+
+use crate::ArbitraryOrd;
+
+/// Key comparison trait, for looking up keys of type `K` by a query of type `Self`.
+///
+/// This is the ordered counterpart of the `Equivalent` trait used by `hashbrown` and `indexmap`.
+/// Any `ArbitraryOrd` type `Q` is comparable to the keys that borrow as `Q`, so every key is
+/// comparable to itself and `str` is comparable to `String`. Other query types implement the
+/// trait directly, often by forwarding to an [`ArbitraryOrd<K>`] impl.
+///
+/// The order must agree with the order of `K`, if `q.compare(a)` is `Less` and `a` is less than
+/// `b` then `q.compare(b)` must also be `Less`, and likewise for `Greater`.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{ArbitraryOrd, Comparable};
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// struct Version {
+///     major: u32,
+///     minor: u32,
+/// }
+///
+/// impl ArbitraryOrd for Version {
+///     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+///         (self.major, self.minor).cmp(&(other.major, other.minor))
+///     }
+/// }
+///
+/// /// Looks up versions by major version only.
+/// struct Major(u32);
+///
+/// impl Comparable<Version> for Major {
+///     fn compare(&self, key: &Version) -> Ordering { self.0.cmp(&key.major) }
+/// }
+///
+/// let v = Version { major: 2, minor: 1 };
+/// assert_eq!(Major(2).compare(&v), Ordering::Equal);
+/// assert_eq!(Major(3).compare(&v), Ordering::Greater);
+/// assert_eq!(v.compare(&Version { major: 2, minor: 3 }), Ordering::Less);
+/// ```
+pub trait Comparable<K: ?Sized> {
+    /// Compares `self` to `key` and returns their ordering.
+    fn compare(&self, key: &K) -> Ordering;
+}
+
+impl<Q: ArbitraryOrd + ?Sized, K: Borrow<Q> + ?Sized> Comparable<K> for Q {
+    fn compare(&self, key: &K) -> Ordering { self.arbitrary_cmp(key.borrow()) }
+}
+
+/// Panics if the range from `start` to `end` is reversed, in the same cases as `BTreeMap::range`.
+pub(crate) fn check_range<Q: ArbitraryOrd + ?Sized>(start: Bound<&Q>, end: Bound<&Q>) {
+    check_range_by(start, end, Q::arbitrary_cmp);
+}
+
+/// Panics if the range from `start` to `end` is reversed according to `cmp`.
+pub(crate) fn check_range_by<Q: ?Sized>(
+    start: Bound<&Q>,
+    end: Bound<&Q>,
+    cmp: impl Fn(&Q, &Q) -> Ordering,
+) {
+    match (start, end) {
+        (Bound::Excluded(s), Bound::Excluded(e)) if cmp(s, e) == Ordering::Equal => {
+            panic!("range start and end are equal and excluded")
+        }
+        (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e))
+            if cmp(s, e) == Ordering::Greater =>
+        {
+            panic!("range start is greater than range end")
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Orderings as values.
+//!
+//! [`ArbitraryOrd`] provides a single ordering per type. A [`Comparator`] is a value that orders
+//! values of some type, comparators can be built on the fly and combined with each other.
+//!
+//! # Examples
+//!
+//! ```
+//! use core::cmp::Ordering;
+//! use ordered::comparator::{self, Comparator};
+//! use ordered::ArbitraryOrd;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! struct Point {
+//!     x: u32,
+//!     y: u32,
+//! }
+//!
+//! impl ArbitraryOrd for Point {
+//!     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+//!         (self.x, self.y).cmp(&(other.x, other.y))
+//!     }
+//! }
+//!
+//! let a = Point { x: 1, y: 5 };
+//! let b = Point { x: 2, y: 3 };
+//!
+//! // Order by `y` descending, then by the `ArbitraryOrd` impl.
+//! let cmp = comparator::natural().by_key(|p: &Point| p.y).reverse().then(comparator::arbitrary());
+//! assert_eq!(cmp.compare(&a, &b), Ordering::Less);
+//!
+//! // `None` sorts after any point.
+//! let cmp = comparator::arbitrary().nulls_last();
+//! assert_eq!(cmp.compare(&None, &Some(a)), Ordering::Greater);
+//! ```
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::ArbitraryOrd;
+
+/// A value that defines an ordering on values of type `T`.
+///
+/// The ordering must be total, with the same requirements as [`Ord`].
+pub trait Comparator<T: ?Sized> {
+    /// Compares `a` to `b`.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+
+    /// Returns a comparator that uses `next` to break ties between values that compare equal.
+    fn then<C: Comparator<T>>(self, next: C) -> Then<Self, C>
+    where
+        Self: Sized,
+    {
+        Then { first: self, next }
+    }
+
+    /// Returns a comparator with the reverse ordering.
+    fn reverse(self) -> Reverse<Self>
+    where
+        Self: Sized,
+    {
+        Reverse(self)
+    }
+
+    /// Returns a comparator that orders `U` by comparing the keys extracted with `f`.
+    fn by_key<U: ?Sized, F: Fn(&U) -> T>(self, f: F) -> ByKey<F, Self>
+    where
+        Self: Sized,
+        T: Sized,
+    {
+        ByKey { f, cmp: self }
+    }
+
+    /// Returns a comparator on `Option<T>` that orders `None` before any `Some`.
+    fn nulls_first(self) -> NullsFirst<Self>
+    where
+        Self: Sized,
+        T: Sized,
+    {
+        NullsFirst(self)
+    }
+
+    /// Returns a comparator on `Option<T>` that orders `None` after any `Some`.
+    fn nulls_last(self) -> NullsLast<Self>
+    where
+        Self: Sized,
+        T: Sized,
+    {
+        NullsLast(self)
+    }
+}
+
+impl<T: ?Sized, C: Comparator<T> + ?Sized> Comparator<T> for &C {
+    fn compare(&self, a: &T, b: &T) -> Ordering { (**self).compare(a, b) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ?Sized, C: Comparator<T> + ?Sized> Comparator<T> for alloc::boxed::Box<C> {
+    fn compare(&self, a: &T, b: &T) -> Ordering { (**self).compare(a, b) }
+}
+
+/// The comparator defined by [`ArbitraryOrd`], created with [`arbitrary()`].
+///
+/// This is a zero-sized type, `T` is only used to guide type inference.
+pub struct ArbitraryOrder<T: ?Sized>(PhantomData<fn(&T, &T) -> Ordering>);
+
+/// Returns the comparator defined by [`ArbitraryOrd`].
+#[must_use]
+pub const fn arbitrary<T: ArbitraryOrd + ?Sized>() -> ArbitraryOrder<T> {
+    ArbitraryOrder(PhantomData)
+}
+
+impl<T: ArbitraryOrd + ?Sized> Comparator<T> for ArbitraryOrder<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering { a.arbitrary_cmp(b) }
+}
+
+/// The comparator defined by [`Ord`], created with [`natural`].
+///
+/// This is a zero-sized type, `T` is only used to guide type inference.
+pub struct NaturalOrder<T: ?Sized>(PhantomData<fn(&T, &T) -> Ordering>);
+
+/// Returns the comparator defined by [`Ord`].
+#[must_use]
+pub const fn natural<T: Ord + ?Sized>() -> NaturalOrder<T> { NaturalOrder(PhantomData) }
+
+impl<T: Ord + ?Sized> Comparator<T> for NaturalOrder<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering { a.cmp(b) }
+}
+
+macro_rules! impl_zst_traits {
+    ($ty:ident, $($sized:tt)*) => {
+        impl<T: $($sized)*> Clone for $ty<T> {
+            fn clone(&self) -> Self { *self }
+        }
+
+        impl<T: $($sized)*> Copy for $ty<T> {}
+
+        impl<T: $($sized)*> Default for $ty<T> {
+            fn default() -> Self { $ty(PhantomData) }
+        }
+
+        impl<T: $($sized)*> fmt::Debug for $ty<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(stringify!($ty)) }
+        }
+    };
+}
+impl_zst_traits!(ArbitraryOrder, ?Sized);
+impl_zst_traits!(NaturalOrder, ?Sized);
+
+/// A comparator backed by a closure, created with [`from_fn`].
+#[derive(Clone, Copy)]
+pub struct FnComparator<F>(F);
+
+/// Creates a comparator from a closure.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::comparator::{self, Comparator};
+///
+/// let by_len = comparator::from_fn(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+/// assert_eq!(by_len.compare(&"abc", &"z"), Ordering::Greater);
+/// ```
+pub fn from_fn<T: ?Sized, F: Fn(&T, &T) -> Ordering>(f: F) -> FnComparator<F> { FnComparator(f) }
+
+impl<T: ?Sized, F: Fn(&T, &T) -> Ordering> Comparator<T> for FnComparator<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering { (self.0)(a, b) }
+}
+
+impl<F> fmt::Debug for FnComparator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("FnComparator") }
+}
+
+/// A comparator that breaks ties using a second comparator, created with [`Comparator::then`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Then<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<T: ?Sized, A: Comparator<T>, B: Comparator<T>> Comparator<T> for Then<A, B> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        match self.first.compare(a, b) {
+            Ordering::Equal => self.next.compare(a, b),
+            ord => ord,
+        }
+    }
+}
+
+/// A comparator with the reverse ordering, created with [`Comparator::reverse`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Reverse<C>(C);
+
+impl<T: ?Sized, C: Comparator<T>> Comparator<T> for Reverse<C> {
+    fn compare(&self, a: &T, b: &T) -> Ordering { self.0.compare(b, a) }
+}
+
+/// A comparator that compares extracted keys, created with [`Comparator::by_key`].
+#[derive(Clone, Copy)]
+pub struct ByKey<F, C> {
+    f: F,
+    cmp: C,
+}
+
+impl<U: ?Sized, K, F: Fn(&U) -> K, C: Comparator<K>> Comparator<U> for ByKey<F, C> {
+    fn compare(&self, a: &U, b: &U) -> Ordering { self.cmp.compare(&(self.f)(a), &(self.f)(b)) }
+}
+
+impl<F, C: fmt::Debug> fmt::Debug for ByKey<F, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ByKey").field("cmp", &self.cmp).finish_non_exhaustive()
+    }
+}
+
+/// A comparator that orders `None` first, created with [`Comparator::nulls_first`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NullsFirst<C>(C);
+
+impl<T, C: Comparator<T>> Comparator<Option<T>> for NullsFirst<C> {
+    fn compare(&self, a: &Option<T>, b: &Option<T>) -> Ordering {
+        match (a, b) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(a), Some(b)) => self.0.compare(a, b),
+        }
+    }
+}
+
+/// A comparator that orders `None` last, created with [`Comparator::nulls_last`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NullsLast<C>(C);
+
+impl<T, C: Comparator<T>> Comparator<Option<T>> for NullsLast<C> {
+    fn compare(&self, a: &Option<T>, b: &Option<T>) -> Ordering {
+        match (a, b) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => self.0.compare(a, b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    impl Point {
+        fn new(x: u32, y: u32) -> Self { Point { x, y } }
+    }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            (self.x, self.y).cmp(&(other.x, other.y))
+        }
+    }
+
+    #[test]
+    fn default_comparators() {
+        assert_eq!(arbitrary().compare(&Point::new(1, 2), &Point::new(1, 3)), Ordering::Less);
+        assert_eq!(natural().compare(&2, &1), Ordering::Greater);
+        assert_eq!(natural().compare("a", "a"), Ordering::Equal);
+        assert_eq!(arbitrary().compare("ab", "b"), Ordering::Less);
+        assert_eq!(arbitrary().compare(&[2u8][..], &[1, 3]), Ordering::Greater);
+    }
+
+    #[test]
+    fn then_breaks_ties() {
+        let cmp = natural().by_key(|p: &Point| p.y).then(arbitrary());
+
+        assert_eq!(cmp.compare(&Point::new(2, 1), &Point::new(1, 1)), Ordering::Greater);
+        assert_eq!(cmp.compare(&Point::new(2, 1), &Point::new(1, 2)), Ordering::Less);
+    }
+
+    #[test]
+    fn reverse_reverses() {
+        let cmp = arbitrary().reverse();
+
+        assert_eq!(cmp.compare(&Point::new(1, 2), &Point::new(1, 3)), Ordering::Greater);
+        assert_eq!(cmp.compare(&Point::new(1, 2), &Point::new(1, 2)), Ordering::Equal);
+    }
+
+    #[test]
+    fn nulls() {
+        let first = arbitrary().nulls_first();
+        let last = arbitrary().nulls_last();
+        let p = Some(Point::new(0, 0));
+
+        assert_eq!(first.compare(&None, &p), Ordering::Less);
+        assert_eq!(last.compare(&None, &p), Ordering::Greater);
+        assert_eq!(first.compare(&None, &None), Ordering::Equal);
+        assert_eq!(last.compare(&p, &Some(Point::new(0, 1))), Ordering::Less);
+    }
+
+    #[test]
+    fn from_fn_and_references() {
+        let cmp = from_fn(|a: &u32, b: &u32| (a % 10).cmp(&(b % 10)));
+
+        assert_eq!(cmp.compare(&19, &21), Ordering::Greater);
+        assert_eq!((&cmp).reverse().compare(&19, &21), Ordering::Less);
+    }
+
+    #[test]
+    fn comparator_is_object_safe() {
+        extern crate std;
+        use std::boxed::Box;
+
+        let cmp: Box<dyn Comparator<u32>> = Box::new(natural().reverse());
+        assert_eq!(cmp.compare(&1, &2), Ordering::Greater);
+        assert_eq!((&*cmp).then(natural()).compare(&1, &1), Ordering::Equal);
+    }
+}
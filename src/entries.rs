@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Searching, iterators and the entry API for maps and sets that keep their entries in a sorted
+//! [`Store`].
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+use core::{fmt, mem, slice};
+
+use crate::comparable::check_range;
+use crate::{ArbitraryOrd, Comparable};
+
+/// Contiguous storage for the sorted entries of a map.
+pub(crate) trait Store<T> {
+    /// Returns the stored elements.
+    fn as_slice(&self) -> &[T];
+
+    /// Returns the stored elements mutably.
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    /// Inserts `value` at `index`, moving the elements after it up by one.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length, or if the store cannot grow.
+    fn insert(&mut self, index: usize, value: T);
+
+    /// Removes the element at `index`, moving the elements after it down by one.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    fn remove(&mut self, index: usize) -> T;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Store<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] { self }
+
+    fn as_mut_slice(&mut self) -> &mut [T] { self }
+
+    fn insert(&mut self, index: usize, value: T) { Vec::insert(self, index, value) }
+
+    fn remove(&mut self, index: usize) -> T { Vec::remove(self, index) }
+}
+
+/// Returns the key of a map entry.
+pub(crate) fn key<K, V>(entry: &(K, V)) -> &K { &entry.0 }
+
+/// Returns a set element, which is its own key.
+pub(crate) fn element<T>(element: &T) -> &T { element }
+
+/// Binary searches `entries` for `q`, returning its index or the index it would be inserted at.
+pub(crate) fn search<E, K, Q>(entries: &[E], key: fn(&E) -> &K, q: &Q) -> Result<usize, usize>
+where
+    Q: Comparable<K> + ?Sized,
+{
+    entries.binary_search_by(|e| q.compare(key(e)).reverse())
+}
+
+/// Returns the sub-slice of `entries` with keys within `range`.
+///
+/// # Panics
+///
+/// If the start of the range is greater than the end, or if they are equal and both excluded.
+pub(crate) fn range<E, K, Q, R>(entries: &[E], key: fn(&E) -> &K, range: R) -> &[E]
+where
+    Q: Comparable<K> + ArbitraryOrd + ?Sized,
+    R: RangeBounds<Q>,
+{
+    let (start, end) = (range.start_bound(), range.end_bound());
+    check_range(start, end);
+    range_by(entries, start, end, |q, e| q.compare(key(e)))
+}
+
+/// Returns the sub-slice of `entries` from `start` to `end`, where `cmp` compares a bound to an
+/// entry.
+///
+/// The bounds must already have been checked to not be reversed.
+pub(crate) fn range_by<'e, E, Q: ?Sized>(
+    entries: &'e [E],
+    start: Bound<&Q>,
+    end: Bound<&Q>,
+    cmp: impl Fn(&Q, &E) -> Ordering,
+) -> &'e [E] {
+    // The index of the first entry for which `pred(cmp(q, entry))` is false.
+    let partition_point =
+        |q: &Q, pred: fn(Ordering) -> bool| entries.partition_point(|e| pred(cmp(q, e)));
+    let start = match start {
+        Bound::Included(q) => partition_point(q, |ord| ord == Ordering::Greater),
+        Bound::Excluded(q) => partition_point(q, |ord| ord != Ordering::Less),
+        Bound::Unbounded => 0,
+    };
+    let end = match end {
+        Bound::Included(q) => partition_point(q, |ord| ord != Ordering::Less),
+        Bound::Excluded(q) => partition_point(q, |ord| ord == Ordering::Greater),
+        Bound::Unbounded => entries.len(),
+    };
+    &entries[start..end]
+}
+
+/// Sorts `entries` by key and merges entries with equal keys, with the same result as inserting
+/// them in order: the first key is kept with the last value.
+#[cfg(feature = "alloc")]
+pub(crate) fn sort_dedup<K, V>(entries: &mut Vec<(K, V)>, cmp: impl Fn(&K, &K) -> Ordering) {
+    entries.sort_by(|(a, _), (b, _)| cmp(a, b));
+    entries.dedup_by(|later, earlier| {
+        let equal = cmp(&later.0, &earlier.0) == Ordering::Equal;
+        if equal {
+            mem::swap(&mut later.1, &mut earlier.1);
+        }
+        equal
+    });
+}
+
+/// Sorts set `elements` and removes all but the first of equal elements, with the same result as
+/// inserting them in order.
+#[cfg(feature = "alloc")]
+pub(crate) fn sort_dedup_elements<T>(elements: &mut Vec<T>, cmp: impl Fn(&T, &T) -> Ordering) {
+    elements.sort_by(&cmp);
+    elements.dedup_by(|later, earlier| cmp(later, earlier) == Ordering::Equal);
+}
+
+/// Implements the iterator traits for an iterator that wraps a slice iterator over entries.
+macro_rules! impl_iterator {
+    ($name:ident, $entries:ty, $item:ty, |$x:pat_param| $map:expr) => {
+        impl<'a, K, V> $name<'a, K, V> {
+            /// Creates an iterator over `entries`.
+            pub(crate) fn new(entries: $entries) -> Self { Self(entries.into_iter()) }
+        }
+
+        impl<'a, K, V> Iterator for $name<'a, K, V> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> { self.0.next().map(|$x| $map) }
+
+            fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+        }
+
+        impl<'a, K, V> DoubleEndedIterator for $name<'a, K, V> {
+            fn next_back(&mut self) -> Option<Self::Item> { self.0.next_back().map(|$x| $map) }
+        }
+
+        impl<'a, K, V> ExactSizeIterator for $name<'a, K, V> {}
+
+        impl<'a, K, V> FusedIterator for $name<'a, K, V> {}
+    };
+}
+
+/// An iterator over the entries of a map, sorted by key.
+///
+/// Created by the `iter` and `range` methods of the map types.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V>(slice::Iter<'a, (K, V)>);
+
+impl_iterator!(Iter, &'a [(K, V)], (&'a K, &'a V), |(k, v)| (k, v));
+
+/// A mutable iterator over the entries of a map, sorted by key.
+///
+/// Created by the `iter_mut` method of the map types.
+#[derive(Debug)]
+pub struct IterMut<'a, K, V>(slice::IterMut<'a, (K, V)>);
+
+impl_iterator!(IterMut, &'a mut [(K, V)], (&'a K, &'a mut V), |(k, v)| (&*k, v));
+
+/// An iterator over the keys of a map, in order.
+///
+/// Created by the `keys` method of the map types.
+#[derive(Debug, Clone)]
+pub struct Keys<'a, K, V>(slice::Iter<'a, (K, V)>);
+
+impl_iterator!(Keys, &'a [(K, V)], &'a K, |(k, _)| k);
+
+/// An iterator over the values of a map, sorted by key.
+///
+/// Created by the `values` method of the map types.
+#[derive(Debug, Clone)]
+pub struct Values<'a, K, V>(slice::Iter<'a, (K, V)>);
+
+impl_iterator!(Values, &'a [(K, V)], &'a V, |(_, v)| v);
+
+/// A mutable iterator over the values of a map, sorted by key.
+///
+/// Created by the `values_mut` method of the map types.
+#[derive(Debug)]
+pub struct ValuesMut<'a, K, V>(slice::IterMut<'a, (K, V)>);
+
+impl_iterator!(ValuesMut, &'a mut [(K, V)], &'a mut V, |(_, v)| v);
+
+/// Returns the entry for `key`, given the result of searching `store` for it.
+pub(crate) fn entry<K, V>(
+    store: &mut dyn Store<(K, V)>,
+    search: Result<usize, usize>,
+    key: K,
+) -> Entry<'_, K, V> {
+    match search {
+        Ok(index) => Entry::Occupied(OccupiedEntry { store, index }),
+        Err(index) => Entry::Vacant(VacantEntry { store, index, key }),
+    }
+}
+
+/// A view into a single entry of a map, which may be vacant or occupied.
+///
+/// Created by the `entry` method of the map types.
+pub enum Entry<'a, K, V> {
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V>),
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Inserts `default` if the entry is vacant and returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V { self.or_insert_with(|| default) }
+
+    /// Inserts the result of `default` if the entry is vacant and returns a mutable reference to
+    /// the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => entry.insert(default()),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Calls `f` on the value if the entry is occupied.
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+        }
+    }
+
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Vacant(entry) => entry.key(),
+            Entry::Occupied(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'a, K, V: Default> Entry<'a, K, V> {
+    /// Inserts the default value if the entry is vacant and returns a mutable reference to the
+    /// value.
+    pub fn or_default(self) -> &'a mut V { self.or_insert_with(V::default) }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Entry<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish(),
+            Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+        }
+    }
+}
+
+/// A view into a vacant entry of a map, where the key is not present.
+pub struct VacantEntry<'a, K, V> {
+    store: &'a mut dyn Store<(K, V)>,
+    /// The index at which the key would be inserted to keep the entries sorted.
+    index: usize,
+    key: K,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Returns the key that would be used when inserting through this entry.
+    pub fn key(&self) -> &K { &self.key }
+
+    /// Takes ownership of the key.
+    pub fn into_key(self) -> K { self.key }
+
+    /// Inserts `value` and returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.store.insert(self.index, (self.key, value));
+        &mut self.store.as_mut_slice()[self.index].1
+    }
+}
+
+impl<K: fmt::Debug, V> fmt::Debug for VacantEntry<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("VacantEntry").field(self.key()).finish()
+    }
+}
+
+/// A view into an occupied entry of a map.
+pub struct OccupiedEntry<'a, K, V> {
+    store: &'a mut dyn Store<(K, V)>,
+    index: usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    #[must_use]
+    pub fn key(&self) -> &K { &self.store.as_slice()[self.index].0 }
+
+    /// Returns a reference to the value of the entry.
+    #[must_use]
+    pub fn get(&self) -> &V { &self.store.as_slice()[self.index].1 }
+
+    /// Returns a mutable reference to the value of the entry.
+    pub fn get_mut(&mut self) -> &mut V { &mut self.store.as_mut_slice()[self.index].1 }
+
+    /// Converts the entry into a mutable reference to its value.
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut V { &mut self.store.as_mut_slice()[self.index].1 }
+
+    /// Sets the value of the entry, returning the old value.
+    pub fn insert(&mut self, value: V) -> V { mem::replace(self.get_mut(), value) }
+
+    /// Removes the entry from the map, returning its value.
+    #[allow(clippy::must_use_candidate)] // Removing the entry is a side effect.
+    pub fn remove(self) -> V { self.remove_entry().1 }
+
+    /// Removes the entry from the map, returning its key and value.
+    #[allow(clippy::must_use_candidate)] // Removing the entry is a side effect.
+    pub fn remove_entry(self) -> (K, V) { self.store.remove(self.index) }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for OccupiedEntry<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OccupiedEntry").field("key", self.key()).field("value", self.get()).finish()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A key type without `Ord`, shared by the tests of the map types.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct Point {
+        pub(crate) x: u32,
+        pub(crate) y: u32,
+    }
+
+    pub(crate) fn p(x: u32, y: u32) -> Point { Point { x, y } }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            (self.x, self.y).cmp(&(other.x, other.y))
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sort_dedup_keeps_first_key_and_last_value() {
+        // Compare only `x`, so that which key is kept can be seen from `y`.
+        let mut entries =
+            alloc::vec![(p(2, 0), 'a'), (p(1, 0), 'b'), (p(2, 1), 'c'), (p(2, 2), 'd')];
+        sort_dedup(&mut entries, |a, b| a.x.cmp(&b.x));
+        assert_eq!(entries, [(p(1, 0), 'b'), (p(2, 0), 'd')]);
+    }
+
+    #[test]
+    fn range_by_bounds() {
+        let entries = [(1, ()), (3, ()), (5, ())];
+        let cmp = |q: &u32, e: &(u32, ())| q.cmp(&e.0);
+        let range = |start, end| range_by(&entries, start, end, cmp).len();
+
+        assert_eq!(range(Bound::Included(&3), Bound::Included(&5)), 2);
+        assert_eq!(range(Bound::Excluded(&3), Bound::Unbounded), 1);
+        assert_eq!(range(Bound::Unbounded, Bound::Excluded(&3)), 1);
+        assert_eq!(range(Bound::Included(&2), Bound::Excluded(&3)), 0);
+    }
+}
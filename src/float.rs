@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Total orders for floating point numbers.
+//!
+//! `f32` and `f64` only implement `PartialOrd` because of `NaN`, and they do not implement `Eq` or
+//! `Hash`. [`Float`] wraps a float together with a [`FloatPolicy`] that decides how `NaN` and signed
+//! zeros are ordered, and implements `ArbitraryOrd`, `Eq` and `Hash` consistently with that policy.
+//! This allows floats to be used with [`Ordered`](crate::Ordered) as `BTreeMap` and `HashMap` keys.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use ordered::float::{Float, NanLast};
+//! use ordered::Ordered;
+//!
+//! let mut map = BTreeMap::new();
+//! map.insert(Ordered(Float::<f64, NanLast>::new(f64::NAN)), "nan");
+//! map.insert(Ordered(Float::new(1.5)), "one and a half");
+//! map.insert(Ordered(Float::new(-0.0)), "zero");
+//!
+//! assert_eq!(map.values().copied().collect::<Vec<_>>(), ["zero", "one and a half", "nan"]);
+//! ```
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use crate::ArbitraryOrd;
+
+/// A primitive floating point type, implemented for `f32` and `f64`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Primitive: Copy + PartialEq + PartialOrd + sealed::Sealed {
+    /// Compares using the IEEE 754 `totalOrder` predicate, see [`f64::total_cmp`].
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    /// Returns `true` if this value is `NaN`.
+    fn is_nan(self) -> bool;
+
+    /// Returns the raw bits of this value, zero extended to 64 bits.
+    fn to_bits(self) -> u64;
+
+    /// Returns `+0.0` if this value is `-0.0` and otherwise returns the value unchanged.
+    #[must_use]
+    fn normalize_zero(self) -> Self;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ident),*) => {
+        $(
+            impl Primitive for $ty {
+                fn total_cmp(&self, other: &Self) -> Ordering { $ty::total_cmp(self, other) }
+                fn is_nan(self) -> bool { $ty::is_nan(self) }
+                fn to_bits(self) -> u64 { $ty::to_bits(self).into() }
+                fn normalize_zero(self) -> Self { if self == 0.0 { 0.0 } else { self } }
+            }
+
+            impl sealed::Sealed for $ty {}
+        )*
+    };
+}
+impl_primitive!(f32, f64);
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A policy that defines a total order, and matching equality and hashing, on floats.
+///
+/// # Implementing
+///
+/// [`FloatPolicy::float_cmp`] must be a total order. [`FloatPolicy::hash_bits`] must return the same
+/// value for any two floats that compare `Equal`.
+pub trait FloatPolicy {
+    /// Compares `a` to `b`.
+    fn float_cmp<F: Primitive>(a: F, b: F) -> Ordering;
+
+    /// Returns the bits that are hashed, these must be equal for values that compare `Equal`.
+    fn hash_bits<F: Primitive>(x: F) -> u64;
+}
+
+/// The IEEE 754 `totalOrder` predicate, as implemented by [`f64::total_cmp`].
+///
+/// Negative `NaN`s order first and positive `NaN`s order last, `-0.0` is less than `+0.0`. Two
+/// floats are only equal if they have the same bit pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TotalOrder {}
+
+impl FloatPolicy for TotalOrder {
+    fn float_cmp<F: Primitive>(a: F, b: F) -> Ordering { a.total_cmp(&b) }
+
+    fn hash_bits<F: Primitive>(x: F) -> u64 { x.to_bits() }
+}
+
+/// All `NaN`s are equal and order before any other value, `-0.0` is less than `+0.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NanFirst {}
+
+impl FloatPolicy for NanFirst {
+    fn float_cmp<F: Primitive>(a: F, b: F) -> Ordering {
+        match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => a.total_cmp(&b),
+        }
+    }
+
+    fn hash_bits<F: Primitive>(x: F) -> u64 { nan_to_max(x) }
+}
+
+/// All `NaN`s are equal and order after any other value, `-0.0` is less than `+0.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NanLast {}
+
+impl FloatPolicy for NanLast {
+    fn float_cmp<F: Primitive>(a: F, b: F) -> Ordering {
+        match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => a.total_cmp(&b),
+        }
+    }
+
+    fn hash_bits<F: Primitive>(x: F) -> u64 { nan_to_max(x) }
+}
+
+/// `-0.0` is equal to `+0.0` and all `NaN`s are equal and order after any other value.
+///
+/// This is the same order as `ordered_float::OrderedFloat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Canonical {}
+
+impl FloatPolicy for Canonical {
+    fn float_cmp<F: Primitive>(a: F, b: F) -> Ordering {
+        NanLast::float_cmp(a.normalize_zero(), b.normalize_zero())
+    }
+
+    fn hash_bits<F: Primitive>(x: F) -> u64 { nan_to_max(x.normalize_zero()) }
+}
+
+/// Returns the bits of `x`, mapping all `NaN`s to the same value.
+fn nan_to_max<F: Primitive>(x: F) -> u64 {
+    if x.is_nan() {
+        u64::MAX
+    } else {
+        x.to_bits()
+    }
+}
+
+/// A float that is totally ordered, compared and hashed according to the policy `P`.
+///
+/// `PartialEq`, `Eq` and `Hash` agree with the order, so for example `Float::<f64, Canonical>`
+/// considers `-0.0` and `+0.0` equal and hashes them the same.
+///
+/// # Examples
+///
+/// ```
+/// use ordered::float::{Canonical, Float, TotalOrder};
+/// use ordered::Ordered;
+///
+/// assert!(Ordered(Float::<f64>::new(-0.0)) < Ordered(Float::new(0.0)));
+/// assert!(Ordered(Float::<f64, Canonical>::new(-0.0)) == Ordered(Float::new(0.0)));
+/// assert!(Float::<f32, TotalOrder>::new(f32::NAN) == Float::new(f32::NAN));
+/// ```
+#[repr(transparent)]
+pub struct Float<T, P = TotalOrder>(pub T, PhantomData<fn() -> P>);
+
+impl<T, P> Float<T, P> {
+    /// Creates a new wrapped float.
+    pub const fn new(inner: T) -> Self { Self(inner, PhantomData) }
+
+    /// Returns the inner float.
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+impl<T: Primitive, P: FloatPolicy> ArbitraryOrd for Float<T, P> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { P::float_cmp(self.0, other.0) }
+}
+
+impl<T: Primitive, P: FloatPolicy> PartialEq for Float<T, P> {
+    fn eq(&self, other: &Self) -> bool { P::float_cmp(self.0, other.0) == Ordering::Equal }
+}
+
+impl<T: Primitive, P: FloatPolicy> Eq for Float<T, P> {}
+
+impl<T: Primitive, P: FloatPolicy> Hash for Float<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) { P::hash_bits(self.0).hash(state) }
+}
+
+impl<T: Clone, P> Clone for Float<T, P> {
+    fn clone(&self) -> Self { Self::new(self.0.clone()) }
+}
+
+impl<T: Copy, P> Copy for Float<T, P> {}
+
+impl<T: Default, P> Default for Float<T, P> {
+    fn default() -> Self { Self::new(T::default()) }
+}
+
+impl<T: fmt::Debug, P> fmt::Debug for Float<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Debug::fmt(&self.0, f) }
+}
+
+impl<T: fmt::Display, P> fmt::Display for Float<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.0, f) }
+}
+
+impl<T, P> From<T> for Float<T, P> {
+    fn from(inner: T) -> Self { Self::new(inner) }
+}
+
+impl<T, P> AsRef<T> for Float<T, P> {
+    fn as_ref(&self) -> &T { &self.0 }
+}
+
+impl<T, P> Deref for Float<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+#[cfg(feature = "ordered-float")]
+mod ordered_float_impls {
+    use ordered_float::{FloatIsNan, NotNan, OrderedFloat};
+
+    use super::Float;
+
+    macro_rules! impl_conversions {
+        ($($ty:ident),*) => {
+            $(
+                impl<P> From<OrderedFloat<$ty>> for Float<$ty, P> {
+                    fn from(f: OrderedFloat<$ty>) -> Self { Self::new(f.0) }
+                }
+
+                impl<P> From<Float<$ty, P>> for OrderedFloat<$ty> {
+                    fn from(f: Float<$ty, P>) -> Self { OrderedFloat(f.0) }
+                }
+
+                impl<P> From<NotNan<$ty>> for Float<$ty, P> {
+                    fn from(f: NotNan<$ty>) -> Self { Self::new(f.into_inner()) }
+                }
+
+                impl<P> TryFrom<Float<$ty, P>> for NotNan<$ty> {
+                    type Error = FloatIsNan;
+
+                    fn try_from(f: Float<$ty, P>) -> Result<Self, Self::Error> { NotNan::new(f.0) }
+                }
+            )*
+        };
+    }
+    impl_conversions!(f32, f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEG_NAN: f64 = -f64::NAN;
+
+    fn cmp<P: FloatPolicy>(a: f64, b: f64) -> Ordering {
+        Float::<f64, P>::new(a).arbitrary_cmp(&Float::new(b))
+    }
+
+    #[test]
+    fn total_order() {
+        assert_eq!(cmp::<TotalOrder>(NEG_NAN, f64::NEG_INFINITY), Ordering::Less);
+        assert_eq!(cmp::<TotalOrder>(-0.0, 0.0), Ordering::Less);
+        assert_eq!(cmp::<TotalOrder>(f64::INFINITY, f64::NAN), Ordering::Less);
+        assert_eq!(cmp::<TotalOrder>(f64::NAN, f64::NAN), Ordering::Equal);
+        assert_ne!(Float::<f64>::new(f64::NAN), Float::new(NEG_NAN));
+    }
+
+    #[test]
+    fn nan_first() {
+        assert_eq!(cmp::<NanFirst>(f64::NAN, f64::NEG_INFINITY), Ordering::Less);
+        assert_eq!(cmp::<NanFirst>(NEG_NAN, f64::NAN), Ordering::Equal);
+        assert_eq!(cmp::<NanFirst>(-0.0, 0.0), Ordering::Less);
+        assert_eq!(cmp::<NanFirst>(1.0, 2.0), Ordering::Less);
+    }
+
+    #[test]
+    fn nan_last() {
+        assert_eq!(cmp::<NanLast>(NEG_NAN, f64::INFINITY), Ordering::Greater);
+        assert_eq!(cmp::<NanLast>(NEG_NAN, f64::NAN), Ordering::Equal);
+        assert_eq!(cmp::<NanLast>(-0.0, 0.0), Ordering::Less);
+        assert_eq!(cmp::<NanLast>(-1.0, 2.0), Ordering::Less);
+    }
+
+    #[test]
+    fn canonical() {
+        assert_eq!(cmp::<Canonical>(-0.0, 0.0), Ordering::Equal);
+        assert_eq!(cmp::<Canonical>(NEG_NAN, f64::NAN), Ordering::Equal);
+        assert_eq!(cmp::<Canonical>(f64::NAN, f64::INFINITY), Ordering::Greater);
+        assert_eq!(cmp::<Canonical>(-0.5, 0.0), Ordering::Less);
+    }
+
+    #[test]
+    fn f32_policies() {
+        let a = Float::<f32, NanFirst>::new(f32::NAN);
+        let b = Float::<f32, NanFirst>::new(f32::MIN);
+
+        assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+        assert_eq!(Float::<f32, Canonical>::new(-0.0), Float::new(0.0));
+    }
+
+    #[test]
+    fn hash_agrees_with_eq() {
+        extern crate std;
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash<P: FloatPolicy>(x: f64) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            Float::<f64, P>::new(x).hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let values = [NEG_NAN, f64::NAN, f64::NEG_INFINITY, -1.0, -0.0, 0.0, 1.0, f64::INFINITY];
+        for a in values {
+            for b in values {
+                if cmp::<TotalOrder>(a, b) == Ordering::Equal {
+                    assert_eq!(hash::<TotalOrder>(a), hash::<TotalOrder>(b));
+                }
+                if cmp::<NanFirst>(a, b) == Ordering::Equal {
+                    assert_eq!(hash::<NanFirst>(a), hash::<NanFirst>(b));
+                }
+                if cmp::<NanLast>(a, b) == Ordering::Equal {
+                    assert_eq!(hash::<NanLast>(a), hash::<NanLast>(b));
+                }
+                if cmp::<Canonical>(a, b) == Ordering::Equal {
+                    assert_eq!(hash::<Canonical>(a), hash::<Canonical>(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn can_be_hash_map_key() {
+        extern crate std;
+        use std::collections::HashMap;
+
+        use crate::Ordered;
+
+        let mut map = HashMap::new();
+        map.insert(Ordered(Float::<f64, Canonical>::new(-0.0)), "zero");
+
+        assert_eq!(map.get(&Ordered(Float::new(0.0))), Some(&"zero"));
+    }
+
+    #[test]
+    #[cfg(feature = "ordered-float")]
+    fn ordered_float_conversions() {
+        use ordered_float::{NotNan, OrderedFloat};
+
+        let f: Float<f64> = OrderedFloat(1.5).into();
+        assert_eq!(OrderedFloat::from(f), OrderedFloat(1.5));
+
+        let f: Float<f64> = NotNan::new(2.5).unwrap().into();
+        assert_eq!(NotNan::try_from(f).unwrap(), NotNan::new(2.5).unwrap());
+        assert!(NotNan::try_from(Float::<f64>::new(f64::NAN)).is_err());
+    }
+}
@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Implements `ArbitraryOrd` for primitive types and standard library containers.
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Cow, ToOwned};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::{self, Ordering};
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use core::hash::{BuildHasher, Hash};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use core::ops::Deref;
+use core::pin::Pin;
+use core::time::Duration;
+
+use crate::ArbitraryOrd;
+
+/// Implements `ArbitraryOrd` by forwarding to `Ord`.
+macro_rules! impl_via_ord {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ArbitraryOrd for $ty {
+                fn arbitrary_cmp(&self, other: &Self) -> Ordering { Ord::cmp(self, other) }
+            }
+        )*
+    };
+}
+impl_via_ord!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_via_ord!(bool, char, (), str, Ordering, Duration);
+impl_via_ord!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+impl_via_ord!(NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+#[cfg(feature = "alloc")]
+impl_via_ord!(String);
+
+#[cfg(feature = "alloc")]
+impl ArbitraryOrd<str> for String {
+    fn arbitrary_cmp(&self, other: &str) -> Ordering { self.as_str().cmp(other) }
+}
+
+#[cfg(feature = "alloc")]
+impl ArbitraryOrd<String> for str {
+    fn arbitrary_cmp(&self, other: &String) -> Ordering { self.cmp(other.as_str()) }
+}
+
+/// An adapter that implements `ArbitraryOrd` for any `Ord` type.
+///
+/// `ArbitraryOrd` is only implemented for a fixed set of `Ord` types from `core` and `alloc`, and
+/// it cannot be implemented for all `Ord` types without conflicting with the impls of other
+/// types. Wrap any other `Ord` type, for example a `std::net::IpAddr` or one of your own types,
+/// in `ByOrd` to use it in a tuple or container that requires `ArbitraryOrd`.
+///
+/// # Examples
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use ordered::{ArbitraryOrd, ByOrd, Ordered};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// impl ArbitraryOrd for Point {
+///     fn arbitrary_cmp(&self, other: &Self) -> core::cmp::Ordering {
+///         (self.x, self.y).cmp(&(other.x, other.y))
+///     }
+/// }
+///
+/// let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+/// let a = Ordered((ByOrd(localhost), Point { x: 1, y: 2 }));
+/// let b = Ordered((ByOrd(localhost), Point { x: 2, y: 1 }));
+/// assert!(a < b);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ByOrd<T>(pub T);
+
+impl<T: Ord> ArbitraryOrd for ByOrd<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+}
+
+/// Reverses the `ArbitraryOrd` of `T`, the same as `Ord`.
+impl<T: ArbitraryOrd> ArbitraryOrd for cmp::Reverse<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { other.0.arbitrary_cmp(&self.0) }
+}
+
+impl<T: ArbitraryOrd + ?Sized> ArbitraryOrd for &mut T {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (**self).arbitrary_cmp(&**other) }
+}
+
+impl<P: Deref> ArbitraryOrd for Pin<P>
+where
+    P::Target: ArbitraryOrd,
+{
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (**self).arbitrary_cmp(&**other) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd + ?Sized> ArbitraryOrd for Box<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (**self).arbitrary_cmp(&**other) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd + ?Sized> ArbitraryOrd for Rc<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (**self).arbitrary_cmp(&**other) }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T: ArbitraryOrd + ?Sized> ArbitraryOrd for Arc<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (**self).arbitrary_cmp(&**other) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd + ToOwned + ?Sized> ArbitraryOrd for Cow<'_, T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (**self).arbitrary_cmp(&**other) }
+}
+
+/// Compares two iterators lexicographically using `ArbitraryOrd`.
+pub(crate) fn cmp_iter<'a, T, I, J>(mut a: I, mut b: J) -> Ordering
+where
+    T: ArbitraryOrd + 'a,
+    I: Iterator<Item = &'a T>,
+    J: Iterator<Item = &'a T>,
+{
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match x.arbitrary_cmp(y) {
+                Ordering::Equal => {}
+                ord => return ord,
+            },
+        }
+    }
+}
+
+impl<T: ArbitraryOrd> ArbitraryOrd for [T] {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { cmp_iter(self.iter(), other.iter()) }
+}
+
+impl<T: ArbitraryOrd, const N: usize> ArbitraryOrd for [T; N] {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().arbitrary_cmp(other.as_slice())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> ArbitraryOrd for Vec<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().arbitrary_cmp(other.as_slice())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> ArbitraryOrd for VecDeque<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { cmp_iter(self.iter(), other.iter()) }
+}
+
+/// `None` is less than any `Some`, the same as `Ord`.
+impl<T: ArbitraryOrd> ArbitraryOrd for Option<T> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(a), Some(b)) => a.arbitrary_cmp(b),
+        }
+    }
+}
+
+/// `Ok` is less than any `Err`, the same as `Ord`.
+impl<T: ArbitraryOrd, E: ArbitraryOrd> ArbitraryOrd for Result<T, E> {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Ok(a), Ok(b)) => a.arbitrary_cmp(b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(a), Err(b)) => a.arbitrary_cmp(b),
+        }
+    }
+}
+
+/// Implements `ArbitraryOrd` lexicographically for tuples.
+macro_rules! impl_tuple {
+    ($($name:ident $idx:tt),+) => {
+        /// Compares the elements lexicographically. Every element must implement `ArbitraryOrd`,
+        /// wrap elements that only implement `Ord` in [`ByOrd`].
+        impl<$($name: ArbitraryOrd),+> ArbitraryOrd for ($($name,)+) {
+            fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+                $(
+                    match self.$idx.arbitrary_cmp(&other.$idx) {
+                        Ordering::Equal => {}
+                        ord => return ord,
+                    }
+                )+
+                Ordering::Equal
+            }
+        }
+    };
+}
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+/// Implements `ArbitraryOrd` for hash based collections.
+///
+/// The contents are sorted before comparing so the result does not depend on iteration order.
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+macro_rules! impl_hash_collections {
+    ($map:path, $set:path) => {
+        impl<K, V, S> ArbitraryOrd for $map
+        where
+            K: ArbitraryOrd + Eq + Hash,
+            V: ArbitraryOrd,
+            S: BuildHasher,
+        {
+            fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+                let mut this = self.iter().collect::<Vec<_>>();
+                let mut that = other.iter().collect::<Vec<_>>();
+                this.sort_unstable_by(|a, b| a.0.arbitrary_cmp(b.0));
+                that.sort_unstable_by(|a, b| a.0.arbitrary_cmp(b.0));
+                this.arbitrary_cmp(&that)
+            }
+        }
+
+        impl<T, S> ArbitraryOrd for $set
+        where
+            T: ArbitraryOrd + Eq + Hash,
+            S: BuildHasher,
+        {
+            fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+                let mut this = self.iter().collect::<Vec<_>>();
+                let mut that = other.iter().collect::<Vec<_>>();
+                this.sort_unstable_by(|a, b| a.arbitrary_cmp(b));
+                that.sort_unstable_by(|a, b| a.arbitrary_cmp(b));
+                this.arbitrary_cmp(&that)
+            }
+        }
+    };
+}
+#[cfg(feature = "std")]
+impl_hash_collections!(std::collections::HashMap<K, V, S>, std::collections::HashSet<T, S>);
+#[cfg(feature = "hashbrown")]
+impl_hash_collections!(hashbrown::HashMap<K, V, S>, hashbrown::HashSet<T, S>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ordered;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    const fn p(x: u32, y: u32) -> Point { Point { x, y } }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            (self.x, self.y).cmp(&(other.x, other.y))
+        }
+    }
+
+    #[test]
+    fn primitives_agree_with_ord() {
+        assert_eq!(1_u8.arbitrary_cmp(&2), Ordering::Less);
+        assert_eq!((-1_i64).arbitrary_cmp(&-2), Ordering::Greater);
+        assert_eq!('a'.arbitrary_cmp(&'a'), Ordering::Equal);
+        assert_eq!("ab".arbitrary_cmp("b"), Ordering::Less);
+        assert_eq!(
+            Duration::from_secs(1).arbitrary_cmp(&Duration::from_millis(999)),
+            Ordering::Greater
+        );
+        let (one, two) = (NonZeroU32::new(1).unwrap(), NonZeroU32::new(2).unwrap());
+        assert_eq!(one.arbitrary_cmp(&two), Ordering::Less);
+        assert_eq!(cmp::Reverse(1).arbitrary_cmp(&cmp::Reverse(2)), Ordering::Greater);
+    }
+
+    #[test]
+    fn slices_and_arrays_are_lexicographic() {
+        let a = [p(0, 1), p(5, 5)];
+        let b = [p(0, 1), p(5, 6)];
+
+        assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+        assert_eq!(a[..].arbitrary_cmp(&b[..1]), Ordering::Greater); // Longer is greater.
+        assert_eq!(a[..0].arbitrary_cmp(&b[..0]), Ordering::Equal);
+        assert!(Ordered(a) < Ordered(b));
+        assert!(Ordered(&a[..]) < Ordered(&b[..]));
+    }
+
+    #[test]
+    fn option_and_result() {
+        assert_eq!(None.arbitrary_cmp(&Some(p(0, 0))), Ordering::Less);
+        assert_eq!(Some(p(0, 1)).arbitrary_cmp(&Some(p(0, 0))), Ordering::Greater);
+
+        let ok: Result<Point, u32> = Ok(p(9, 9));
+        let err: Result<Point, u32> = Err(0);
+        assert_eq!(ok.arbitrary_cmp(&err), Ordering::Less);
+        assert_eq!(err.arbitrary_cmp(&Err(1)), Ordering::Less);
+    }
+
+    #[test]
+    fn tuples_mix_arbitrary_and_ord() {
+        let a = (p(1, 1), 5_u32);
+        let b = (p(1, 1), 6_u32);
+
+        assert!(Ordered(a) < Ordered(b));
+        assert_eq!((1, 'a', p(0, 0)).arbitrary_cmp(&(1, 'a', p(0, 0))), Ordering::Equal);
+
+        let a = (ByOrd(cmp::Reverse(5_u32)), p(0, 0));
+        let b = (ByOrd(cmp::Reverse(6_u32)), p(0, 0));
+        assert_eq!(a.arbitrary_cmp(&b), Ordering::Greater);
+
+        let big = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, p(0, 0));
+        let bigger = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, p(0, 1));
+        assert_eq!(big.arbitrary_cmp(&bigger), Ordering::Less);
+    }
+
+    #[test]
+    fn mut_references_and_pin() {
+        let mut a = p(0, 1);
+        let mut b = p(1, 0);
+
+        assert!(Ordered(&mut a) < Ordered(&mut b));
+        assert_eq!(Pin::new(&a).arbitrary_cmp(&Pin::new(&b)), Ordering::Less);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn smart_pointers() {
+        use alloc::vec;
+
+        let a = p(0, 1);
+        let b = p(1, 0);
+
+        assert!(Ordered(Box::new(a)) < Ordered(Box::new(b)));
+        assert!(Ordered(Rc::new(a)) < Ordered(Rc::new(b)));
+        assert!(Ordered(Arc::new(a)) < Ordered(Arc::new(b)));
+        assert!(Ordered(Box::pin(a)) < Ordered(Box::pin(b)));
+
+        let owned: Cow<'_, [Point]> = Cow::Owned(vec![a, b]);
+        let borrowed: Cow<'_, [Point]> = Cow::Borrowed(&[a]);
+        assert!(Ordered(borrowed) < Ordered(owned));
+
+        let slice: Box<[Point]> = vec![b].into_boxed_slice();
+        assert!(Ordered(slice) > Ordered(vec![a, b].into_boxed_slice()));
+
+        let slice: Arc<[Point]> = Arc::from(vec![a]);
+        assert!(Ordered(slice) < Ordered(Arc::from(vec![b])));
+
+        let s: Box<str> = "abc".into();
+        assert!(Ordered(s) < Ordered("abd".into()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn std_hash_collections_ignore_insertion_order() {
+        use std::collections::hash_map::RandomState;
+        use std::collections::{HashMap, HashSet};
+
+        let points = [p(3, 0), p(0, 1), p(2, 2), p(0, 0)];
+
+        let a = points.iter().copied().collect::<HashSet<_>>();
+        let b = points.iter().rev().copied().collect::<HashSet<_>>();
+        assert_eq!(a.arbitrary_cmp(&b), Ordering::Equal);
+
+        let c = points[..3].iter().copied().collect::<HashSet<_>>();
+        assert_eq!(a.arbitrary_cmp(&c), Ordering::Less); // First differs at `p(0, 0)`.
+
+        let mut map_a = HashMap::with_hasher(RandomState::new());
+        let mut map_b = HashMap::with_hasher(RandomState::new());
+        for (i, point) in points.iter().enumerate() {
+            map_a.insert(*point, i);
+        }
+        for (i, point) in points.iter().enumerate().rev() {
+            map_b.insert(*point, i);
+        }
+        assert_eq!(map_a.arbitrary_cmp(&map_b), Ordering::Equal);
+
+        *map_b.get_mut(&p(0, 0)).unwrap() += 1;
+        assert_eq!(map_a.arbitrary_cmp(&map_b), Ordering::Less);
+        assert!(Ordered(map_a) < Ordered(map_b));
+    }
+
+    #[test]
+    #[cfg(feature = "hashbrown")]
+    fn hashbrown_collections_ignore_insertion_order() {
+        use core::hash::BuildHasherDefault;
+
+        use hashbrown::HashSet;
+
+        /// A deterministic hasher, `hashbrown` has no default hasher without its `ahash` feature.
+        #[derive(Default)]
+        struct Fnv(u64);
+
+        impl core::hash::Hasher for Fnv {
+            fn finish(&self) -> u64 { self.0 }
+            fn write(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+                }
+            }
+        }
+
+        type Set = HashSet<Point, BuildHasherDefault<Fnv>>;
+
+        let points = [p(3, 0), p(0, 1), p(2, 2), p(0, 0)];
+        let a = points.iter().copied().collect::<Set>();
+        let b = points.iter().rev().copied().collect::<Set>();
+        let c = points[1..].iter().copied().collect::<Set>();
+
+        assert_eq!(a.arbitrary_cmp(&b), Ordering::Equal);
+        assert_eq!(a.arbitrary_cmp(&c), Ordering::Greater); // First differs at `p(3, 0)`.
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn alloc_containers() {
+        use alloc::vec;
+
+        let a = vec![p(0, 0), p(1, 1)];
+        let b = vec![p(0, 0), p(1, 1), p(0, 0)];
+        assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+
+        let a: VecDeque<_> = a.into_iter().collect();
+        let b: VecDeque<_> = b.into_iter().collect();
+        assert_eq!(b.arbitrary_cmp(&a), Ordering::Greater);
+
+        assert_eq!(String::from("a").arbitrary_cmp(&String::from("a")), Ordering::Equal);
+        assert_eq!(String::from("a").arbitrary_cmp("b"), Ordering::Less);
+        assert_eq!("b".arbitrary_cmp(&String::from("a")), Ordering::Greater);
+    }
+}
@@ -0,0 +1,537 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides [`ArbitraryIteratorExt`] and the iterator adapters it returns.
+//!
+//! None of the methods allocate, they are all available without the `alloc` feature.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::{Fuse, FusedIterator, Take};
+
+use crate::ArbitraryOrd;
+
+/// Extension trait for iterators over [`ArbitraryOrd`] types.
+///
+/// Each method is the same as the `Iterator` method without the `arbitrary_` prefix, using the
+/// order defined by `ArbitraryOrd` instead of `Ord`.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{ArbitraryIteratorExt, ArbitraryOrd};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// impl ArbitraryOrd for Point {
+///     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+///         (self.x, self.y).cmp(&(other.x, other.y))
+///     }
+/// }
+///
+/// let points = [Point { x: 5, y: 7 }, Point { x: 2, y: 3 }, Point { x: 5, y: 1 }];
+///
+/// assert_eq!(points.iter().arbitrary_min(), Some(&Point { x: 2, y: 3 }));
+/// assert_eq!(points.iter().arbitrary_max(), Some(&Point { x: 5, y: 7 }));
+/// assert!(!points.iter().is_arbitrarily_sorted());
+/// ```
+pub trait ArbitraryIteratorExt: Iterator
+where
+    Self::Item: ArbitraryOrd,
+{
+    /// Returns the minimum element of the iterator.
+    ///
+    /// If several elements are equally minimum, the first element is returned.
+    fn arbitrary_min(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.min_by(Self::Item::arbitrary_cmp)
+    }
+
+    /// Returns the maximum element of the iterator.
+    ///
+    /// If several elements are equally maximum, the last element is returned.
+    fn arbitrary_max(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.max_by(Self::Item::arbitrary_cmp)
+    }
+
+    /// Returns the minimum and maximum elements of the iterator, in a single pass.
+    ///
+    /// Ties are broken the same way as [`arbitrary_min`] and [`arbitrary_max`]. If the iterator
+    /// has a single element it is returned as both the minimum and the maximum.
+    ///
+    /// [`arbitrary_min`]: ArbitraryIteratorExt::arbitrary_min
+    /// [`arbitrary_max`]: ArbitraryIteratorExt::arbitrary_max
+    fn arbitrary_minmax(mut self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        let first = self.next()?;
+        let minmax = self.fold((first.clone(), first), |(min, max), item| {
+            if Self::Item::arbitrary_cmp(&item, &min) == Ordering::Less {
+                (item, max)
+            } else if Self::Item::arbitrary_cmp(&item, &max) != Ordering::Less {
+                (min, item)
+            } else {
+                (min, max)
+            }
+        });
+        Some(minmax)
+    }
+
+    /// Compares the elements of this iterator with those of `other` lexicographically.
+    fn arbitrary_cmp_iter<I>(mut self, other: I) -> Ordering
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Self::Item>,
+    {
+        let mut other = other.into_iter();
+        loop {
+            match (self.next(), other.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(x), Some(y)) => match Self::Item::arbitrary_cmp(&x, &y) {
+                    Ordering::Equal => {}
+                    ord => return ord,
+                },
+            }
+        }
+    }
+
+    /// Returns `true` if the elements of the iterator are sorted.
+    #[allow(clippy::wrong_self_convention)] // Same as `Iterator::is_sorted`.
+    fn is_arbitrarily_sorted(mut self) -> bool
+    where
+        Self: Sized,
+    {
+        let mut prev = match self.next() {
+            Some(item) => item,
+            None => return true,
+        };
+        self.all(|item| {
+            let sorted = Self::Item::arbitrary_cmp(&prev, &item) != Ordering::Greater;
+            prev = item;
+            sorted
+        })
+    }
+
+    /// Returns an iterator over runs of consecutive elements that compare `Equal`.
+    ///
+    /// Each run is yielded as an iterator over the elements in it. The iterator is cloned to scan
+    /// ahead instead of buffering the run, so this does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ordered::ArbitraryIteratorExt;
+    ///
+    /// let mut runs = [1, 1, 2, 3, 3, 3].iter().arbitrary_runs().map(Iterator::count);
+    ///
+    /// assert_eq!(runs.next(), Some(2));
+    /// assert_eq!(runs.next(), Some(1));
+    /// assert_eq!(runs.next(), Some(3));
+    /// assert_eq!(runs.next(), None);
+    /// ```
+    fn arbitrary_runs(self) -> Runs<Self>
+    where
+        Self: Sized + Clone,
+    {
+        Runs { iter: self }
+    }
+
+    /// Returns an iterator that checks the elements are sorted, yielding an error if they are not.
+    ///
+    /// Adjacent elements may compare `Equal`. The iterator looks ahead one element, if it is less
+    /// than the current element an [`UnsortedError`] holding both is yielded in place of the
+    /// current element, after which the iterator ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ordered::ArbitraryIteratorExt;
+    ///
+    /// let mut iter = [1, 2, 2, 1].into_iter().check_sorted();
+    ///
+    /// assert_eq!(iter.next(), Some(Ok(1)));
+    /// assert_eq!(iter.next(), Some(Ok(2)));
+    ///
+    /// let err = iter.next().unwrap().unwrap_err();
+    /// assert_eq!((err.index, err.prev, err.next), (3, 2, 1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    fn check_sorted(self) -> CheckSorted<Self>
+    where
+        Self: Sized,
+    {
+        CheckSorted::new(self, false)
+    }
+
+    /// Returns an iterator that checks the elements are strictly sorted, yielding an error if they
+    /// are not.
+    ///
+    /// The same as [`check_sorted`] except adjacent elements that compare `Equal` are an error.
+    ///
+    /// [`check_sorted`]: ArbitraryIteratorExt::check_sorted
+    fn check_strictly_sorted(self) -> CheckSorted<Self>
+    where
+        Self: Sized,
+    {
+        CheckSorted::new(self, true)
+    }
+
+    /// Returns an iterator that panics if the elements are not sorted.
+    ///
+    /// # Panics
+    ///
+    /// When advanced to an element that is greater than the one after it.
+    fn expect_sorted(self) -> ExpectSorted<Self>
+    where
+        Self: Sized,
+    {
+        ExpectSorted(self.check_sorted())
+    }
+
+    /// Returns an iterator that panics if the elements are not strictly sorted.
+    ///
+    /// # Panics
+    ///
+    /// When advanced to an element that is greater than or equal to the one after it.
+    fn expect_strictly_sorted(self) -> ExpectSorted<Self>
+    where
+        Self: Sized,
+    {
+        ExpectSorted(self.check_strictly_sorted())
+    }
+}
+
+impl<I: Iterator> ArbitraryIteratorExt for I where I::Item: ArbitraryOrd {}
+
+/// An iterator over runs of consecutive elements that compare `Equal`.
+///
+/// Created by [`ArbitraryIteratorExt::arbitrary_runs`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Runs<I> {
+    iter: I,
+}
+
+impl<I> Iterator for Runs<I>
+where
+    I: Iterator + Clone,
+    I::Item: ArbitraryOrd,
+{
+    type Item = Take<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.iter.clone();
+        let mut prev = self.iter.next()?;
+        let mut len = 1;
+
+        for item in self.iter.clone() {
+            if I::Item::arbitrary_cmp(&prev, &item) != Ordering::Equal {
+                break;
+            }
+            prev = item;
+            len += 1;
+        }
+        if len > 1 {
+            self.iter.nth(len - 2);
+        }
+        Some(start.take(len))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (usize::from(lower > 0), upper)
+    }
+}
+
+impl<I> FusedIterator for Runs<I>
+where
+    I: FusedIterator + Clone,
+    I::Item: ArbitraryOrd,
+{
+}
+
+/// An iterator that checks its elements are sorted.
+///
+/// Created by [`ArbitraryIteratorExt::check_sorted`] and
+/// [`ArbitraryIteratorExt::check_strictly_sorted`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CheckSorted<I: Iterator> {
+    iter: Fuse<I>,
+    peeked: Option<I::Item>,
+    index: usize,
+    strict: bool,
+    failed: bool,
+}
+
+impl<I: Iterator> CheckSorted<I> {
+    fn new(iter: I, strict: bool) -> Self {
+        Self { iter: iter.fuse(), peeked: None, index: 0, strict, failed: false }
+    }
+}
+
+impl<I> Iterator for CheckSorted<I>
+where
+    I: Iterator,
+    I::Item: ArbitraryOrd,
+{
+    type Item = Result<I::Item, UnsortedError<I::Item>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let current = match self.peeked.take() {
+            Some(item) => item,
+            None => self.iter.next()?,
+        };
+        self.index += 1;
+
+        let next = match self.iter.next() {
+            Some(item) => item,
+            None => return Some(Ok(current)),
+        };
+        match I::Item::arbitrary_cmp(&current, &next) {
+            Ordering::Less => {}
+            Ordering::Equal if !self.strict => {}
+            _ => {
+                self.failed = true;
+                return Some(Err(UnsortedError { index: self.index, prev: current, next }));
+            }
+        }
+        self.peeked = Some(next);
+        Some(Ok(current))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.failed {
+            return (0, Some(0));
+        }
+        let peeked = usize::from(self.peeked.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        // Any element may be the last one if the next is out of order.
+        (usize::from(lower > 0 || peeked > 0), upper.and_then(|n| n.checked_add(peeked)))
+    }
+}
+
+impl<I> FusedIterator for CheckSorted<I>
+where
+    I: Iterator,
+    I::Item: ArbitraryOrd,
+{
+}
+
+/// An iterator that panics if its elements are not sorted.
+///
+/// Created by [`ArbitraryIteratorExt::expect_sorted`] and
+/// [`ArbitraryIteratorExt::expect_strictly_sorted`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExpectSorted<I: Iterator>(CheckSorted<I>);
+
+impl<I: Iterator + Clone> Clone for ExpectSorted<I>
+where
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<I: Iterator + fmt::Debug> fmt::Debug for ExpectSorted<I>
+where
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ExpectSorted").field(&self.0).finish()
+    }
+}
+
+impl<I> Iterator for ExpectSorted<I>
+where
+    I: Iterator,
+    I::Item: ArbitraryOrd + fmt::Debug,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.0.next()? {
+            Ok(item) => Some(item),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl<I> FusedIterator for ExpectSorted<I>
+where
+    I: Iterator,
+    I::Item: ArbitraryOrd + fmt::Debug,
+{
+}
+
+/// Error yielded by [`CheckSorted`] when two adjacent elements are out of order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnsortedError<T> {
+    /// The index of `next`, the first element that is out of order.
+    pub index: usize,
+    /// The element before `next`.
+    pub prev: T,
+    /// The element that is not greater than, or equal to if allowed, `prev`.
+    pub next: T,
+}
+
+impl<T: fmt::Debug> fmt::Display for UnsortedError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "element {:?} at index {} is out of order after {:?}",
+            self.next, self.index, self.prev
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for UnsortedError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    fn p(x: u32, y: u32) -> Point { Point { x, y } }
+
+    /// Compares only the `x` of a point, so points with the same `x` are `Equal`.
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.x.cmp(&other.x) }
+    }
+
+    #[test]
+    fn min_max_ties() {
+        let points = [p(1, 0), p(0, 0), p(2, 0), p(0, 1), p(2, 1)];
+
+        assert_eq!(points.iter().arbitrary_min(), Some(&p(0, 0)));
+        assert_eq!(points.iter().arbitrary_max(), Some(&p(2, 1)));
+        assert_eq!(points.iter().arbitrary_minmax(), Some((&p(0, 0), &p(2, 1))));
+        assert_eq!(points.into_iter().arbitrary_minmax(), Some((p(0, 0), p(2, 1))));
+    }
+
+    #[test]
+    fn min_max_edge_cases() {
+        let empty: [Point; 0] = [];
+        assert_eq!(empty.iter().arbitrary_min(), None);
+        assert_eq!(empty.iter().arbitrary_max(), None);
+        assert_eq!(empty.iter().arbitrary_minmax(), None);
+        assert_eq!([p(3, 3)].iter().arbitrary_minmax(), Some((&p(3, 3), &p(3, 3))));
+    }
+
+    #[test]
+    fn cmp_iter() {
+        let a = [p(1, 0), p(2, 0)];
+
+        assert_eq!(a.iter().arbitrary_cmp_iter(&[p(1, 9), p(2, 9)]), Ordering::Equal);
+        assert_eq!(a.iter().arbitrary_cmp_iter(&[p(1, 0)]), Ordering::Greater);
+        assert_eq!(a.iter().arbitrary_cmp_iter(&[p(1, 0), p(2, 0), p(0, 0)]), Ordering::Less);
+        assert_eq!(a.iter().arbitrary_cmp_iter(&[p(0, 0), p(9, 0)]), Ordering::Greater);
+        assert_eq!(a.into_iter().arbitrary_cmp_iter([p(1, 0), p(3, 0)]), Ordering::Less);
+    }
+
+    #[test]
+    fn sorted() {
+        assert!([p(0, 9), p(0, 0), p(1, 5)].iter().is_arbitrarily_sorted());
+        assert!(![p(1, 0), p(0, 0)].iter().is_arbitrarily_sorted());
+        assert!([p(1, 0)].iter().is_arbitrarily_sorted());
+        assert!(core::iter::empty::<Point>().is_arbitrarily_sorted());
+    }
+
+    #[test]
+    fn runs() {
+        let points = [p(0, 0), p(0, 1), p(1, 0), p(0, 2), p(0, 3), p(0, 4)];
+        let mut runs = points.iter().arbitrary_runs();
+
+        assert!(runs.next().unwrap().eq(&[p(0, 0), p(0, 1)]));
+        assert!(runs.next().unwrap().eq(&[p(1, 0)]));
+        assert!(runs.next().unwrap().eq(&[p(0, 2), p(0, 3), p(0, 4)]));
+        assert!(runs.next().is_none());
+        assert!(runs.next().is_none());
+
+        assert_eq!(core::iter::empty::<Point>().arbitrary_runs().count(), 0);
+    }
+
+    #[test]
+    fn check_sorted() {
+        let points = [p(0, 0), p(1, 0), p(1, 1), p(0, 9), p(5, 5)];
+
+        let mut iter = points.into_iter().check_sorted();
+        assert_eq!(iter.next(), Some(Ok(p(0, 0))));
+        assert_eq!(iter.next(), Some(Ok(p(1, 0))));
+        assert_eq!(
+            iter.next(),
+            Some(Err(UnsortedError { index: 3, prev: p(1, 1), next: p(0, 9) }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+
+        let mut iter = points.into_iter().check_strictly_sorted();
+        assert_eq!(iter.next(), Some(Ok(p(0, 0))));
+        assert_eq!(
+            iter.next(),
+            Some(Err(UnsortedError { index: 2, prev: p(1, 0), next: p(1, 1) }))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn check_sorted_passes_through() {
+        let points = [p(0, 0), p(1, 0), p(2, 0)];
+
+        assert!(points.iter().check_strictly_sorted().map(Result::unwrap).eq(&points));
+        assert!(points.iter().expect_strictly_sorted().eq(&points));
+        assert!(points.iter().rev().take(1).expect_strictly_sorted().eq(&points[2..]));
+        assert_eq!(core::iter::empty::<Point>().check_sorted().next(), None);
+    }
+
+    #[test]
+    fn check_sorted_unbounded_size_hint() {
+        let mut iter = core::iter::repeat(p(1, 1)).check_sorted();
+        assert_eq!(iter.next(), Some(Ok(p(1, 1))));
+        assert_eq!(iter.size_hint(), (1, None));
+        assert_eq!(iter.take(3).count(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "at index 2 is out of order")]
+    fn expect_sorted_panics() { for _ in [p(0, 0), p(2, 0), p(1, 0)].iter().expect_sorted() {} }
+
+    #[test]
+    #[should_panic(expected = "at index 1 is out of order")]
+    fn expect_strictly_sorted_panics() {
+        for _ in [p(0, 0), p(0, 1)].iter().expect_strictly_sorted() {}
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn unsorted_error_message() {
+        use alloc::string::ToString;
+
+        let err = [2, 1].into_iter().check_sorted().find_map(Result::err).unwrap();
+        assert_eq!(err.to_string(), "element 1 at index 1 is out of order after 2");
+    }
+}
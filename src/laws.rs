@@ -0,0 +1,535 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Checks that [`ArbitraryOrd`] implementations are total orders.
+//!
+//! A broken `arbitrary_cmp` silently corrupts ordered collections such as
+//! `BTreeMap<Ordered<T>, V>`. The functions in this module check triples of values for the [`Law`]s
+//! that `arbitrary_cmp` must uphold: it must be a total order that agrees with `PartialEq` (and
+//! optionally `Hash`).
+//!
+//! With the `proptest` feature the triples are generated from a `proptest` strategy, and failing
+//! triples are shrunk so the reported counterexample is minimal. With the `arbitrary` feature the
+//! triples are generated from fuzzer input.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "proptest")]
+use std::string::ToString;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "proptest")]
+use proptest::strategy::Strategy;
+#[cfg(feature = "proptest")]
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+
+use crate::ArbitraryOrd;
+
+/// A law that `ArbitraryOrd` implementations must uphold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Law {
+    /// `a.arbitrary_cmp(a)` is `Equal`.
+    Reflexivity,
+    /// If `a <= b` and `b <= a` then `a.arbitrary_cmp(b)` is `Equal`.
+    Antisymmetry,
+    /// If `a <= b` and `b <= c` then `a <= c`, and the same for `<` and `==`.
+    Transitivity,
+    /// `a.arbitrary_cmp(b)` is the reverse of `b.arbitrary_cmp(a)`.
+    Duality,
+    /// `a.arbitrary_cmp(b)` is `Equal` if and only if `a == b`.
+    ConsistentWithEq,
+    /// If `a.arbitrary_cmp(b)` is `Equal` then `a` and `b` have the same hash.
+    ConsistentWithHash,
+}
+
+impl fmt::Display for Law {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Law::Reflexivity => "reflexivity",
+            Law::Antisymmetry => "antisymmetry",
+            Law::Transitivity => "transitivity",
+            Law::Duality => "duality of `Less` and `Greater`",
+            Law::ConsistentWithEq => "consistency with `PartialEq`",
+            Law::ConsistentWithHash => "consistency with `Hash`",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A minimal triple of values that violates one of the [`Law`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LawViolation<T> {
+    /// The law that was violated.
+    pub law: Law,
+    /// The values that violate `law`.
+    pub values: [T; 3],
+}
+
+impl<T: fmt::Debug> fmt::Display for LawViolation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c] = &self.values;
+        write!(f, "`ArbitraryOrd` violates {} for {:?}, {:?}, {:?}", self.law, a, b, c)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for LawViolation<T> {}
+
+/// Checks the laws, except consistency with `Hash`, for a single triple of values.
+///
+/// # Errors
+///
+/// Returns the first law that is violated.
+pub fn check_triple<T: ArbitraryOrd + ?Sized>(a: &T, b: &T, c: &T) -> Result<(), Law> {
+    let values = [a, b, c];
+
+    for x in values {
+        if x.arbitrary_cmp(x) != Ordering::Equal {
+            return Err(Law::Reflexivity);
+        }
+    }
+    for x in values {
+        for y in values {
+            let (xy, yx) = (x.arbitrary_cmp(y), y.arbitrary_cmp(x));
+            if xy != Ordering::Greater && yx != Ordering::Greater && xy != Ordering::Equal {
+                return Err(Law::Antisymmetry);
+            }
+            if xy != yx.reverse() {
+                return Err(Law::Duality);
+            }
+            if (xy == Ordering::Equal) != (x == y) {
+                return Err(Law::ConsistentWithEq);
+            }
+        }
+    }
+    for x in values {
+        for y in values {
+            for z in values {
+                let (xy, yz, xz) = (x.arbitrary_cmp(y), y.arbitrary_cmp(z), x.arbitrary_cmp(z));
+                // Together with duality this covers `>` and `>=` as well.
+                let transitive = match (xy, yz) {
+                    (Ordering::Equal, ord) | (ord, Ordering::Equal) => xz == ord,
+                    (Ordering::Less, Ordering::Less) => xz == Ordering::Less,
+                    _ => true,
+                };
+                if !transitive {
+                    return Err(Law::Transitivity);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the laws, including consistency with `Hash`, for a single triple of values.
+///
+/// # Errors
+///
+/// Returns the first law that is violated.
+pub fn check_triple_with_hash<T: ArbitraryOrd + Hash + ?Sized>(
+    a: &T,
+    b: &T,
+    c: &T,
+) -> Result<(), Law> {
+    check_triple(a, b, c)?;
+
+    let values = [a, b, c];
+    for x in values {
+        for y in values {
+            if x.arbitrary_cmp(y) == Ordering::Equal && hash(x) != hash(y) {
+                return Err(Law::ConsistentWithHash);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Checks the laws, except consistency with `Hash`, for triples of values generated by `strategy`.
+///
+/// # Errors
+///
+/// Returns the minimal counterexample found.
+///
+/// # Panics
+///
+/// If `proptest` aborts the test, for example because `strategy` rejected too many values.
+#[cfg(feature = "proptest")]
+pub fn check<S>(strategy: S) -> Result<(), LawViolation<S::Value>>
+where
+    S: Strategy,
+    S::Value: ArbitraryOrd,
+{
+    run(strategy, |[a, b, c]| check_triple(a, b, c))
+}
+
+/// Checks the laws, including consistency with `Hash`, for triples of values generated by
+/// `strategy`.
+///
+/// # Errors
+///
+/// Returns the minimal counterexample found.
+///
+/// # Panics
+///
+/// If `proptest` aborts the test, for example because `strategy` rejected too many values.
+#[cfg(feature = "proptest")]
+pub fn check_with_hash<S>(strategy: S) -> Result<(), LawViolation<S::Value>>
+where
+    S: Strategy,
+    S::Value: ArbitraryOrd + Hash,
+{
+    run(strategy, |[a, b, c]| check_triple_with_hash(a, b, c))
+}
+
+/// Asserts the laws, except consistency with `Hash`, for triples of values generated by
+/// `strategy`.
+///
+/// # Panics
+///
+/// With the minimal counterexample if any law is violated.
+#[cfg(feature = "proptest")]
+#[track_caller]
+pub fn assert_laws<S>(strategy: S)
+where
+    S: Strategy,
+    S::Value: ArbitraryOrd,
+{
+    if let Err(e) = check(strategy) {
+        panic!("{}", e)
+    }
+}
+
+/// Asserts the laws, including consistency with `Hash`, for triples of values generated by
+/// `strategy`.
+///
+/// # Panics
+///
+/// With the minimal counterexample if any law is violated.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{laws, ArbitraryOrd};
+/// use proptest::prelude::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// impl ArbitraryOrd for Point {
+///     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+///         (self.x, self.y).cmp(&(other.x, other.y))
+///     }
+/// }
+///
+/// let points = (any::<u32>(), any::<u32>()).prop_map(|(x, y)| Point { x, y });
+/// laws::assert_laws_with_hash(points);
+/// ```
+#[cfg(feature = "proptest")]
+#[track_caller]
+pub fn assert_laws_with_hash<S>(strategy: S)
+where
+    S: Strategy,
+    S::Value: ArbitraryOrd + Hash,
+{
+    if let Err(e) = check_with_hash(strategy) {
+        panic!("{}", e)
+    }
+}
+
+/// Runs `check` on triples generated by `strategy`, shrinking any failure.
+#[cfg(feature = "proptest")]
+fn run<S, F>(strategy: S, check: F) -> Result<(), LawViolation<S::Value>>
+where
+    S: Strategy,
+    F: Fn([&S::Value; 3]) -> Result<(), Law>,
+{
+    // Failure persistence needs the source file of the test, which is not known here, and would
+    // print a warning on every call.
+    let mut runner = TestRunner::new(Config { failure_persistence: None, ..Config::default() });
+    let triples = proptest::array::uniform3(strategy);
+
+    let result = runner.run(&triples, |[a, b, c]| {
+        check([&a, &b, &c]).map_err(|law| TestCaseError::fail(law.to_string()))
+    });
+    match result {
+        Ok(()) => Ok(()),
+        Err(TestError::Fail(_, values)) => {
+            let [a, b, c] = &values;
+            // Shrinking may have changed which law fails, check the minimal triple again.
+            let law = check([a, b, c]).expect_err("minimal triple fails");
+            Err(LawViolation { law, values })
+        }
+        Err(TestError::Abort(reason)) => panic!("proptest aborted: {}", reason),
+    }
+}
+
+/// Checks the laws, except consistency with `Hash`, for a triple of values generated from fuzzer
+/// input.
+///
+/// # Errors
+///
+/// If `u` does not contain enough data to generate the values.
+///
+/// # Panics
+///
+/// If any law is violated, so the fuzzer records the input as a crash.
+///
+/// # Examples
+///
+/// ```
+/// use arbitrary::Unstructured;
+/// use ordered::laws;
+///
+/// // Usually the data comes from the fuzzer, e.g. inside `fuzz_target!(|data: &[u8]| { ... })`.
+/// let data = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc];
+/// let _ = laws::fuzz::<u16>(&mut Unstructured::new(&data));
+/// ```
+#[cfg(feature = "arbitrary")]
+#[track_caller]
+pub fn fuzz<'a, T>(u: &mut Unstructured<'a>) -> arbitrary::Result<()>
+where
+    T: Arbitrary<'a> + ArbitraryOrd + fmt::Debug,
+{
+    fuzz_with::<T, _>(u, |[a, b, c]| check_triple(a, b, c))
+}
+
+/// Checks the laws, including consistency with `Hash`, for a triple of values generated from
+/// fuzzer input.
+///
+/// # Errors
+///
+/// If `u` does not contain enough data to generate the values.
+///
+/// # Panics
+///
+/// If any law is violated, so the fuzzer records the input as a crash.
+#[cfg(feature = "arbitrary")]
+#[track_caller]
+pub fn fuzz_with_hash<'a, T>(u: &mut Unstructured<'a>) -> arbitrary::Result<()>
+where
+    T: Arbitrary<'a> + ArbitraryOrd + Hash + fmt::Debug,
+{
+    fuzz_with::<T, _>(u, |[a, b, c]| check_triple_with_hash(a, b, c))
+}
+
+/// Generates a triple from `u` and runs `check` on it.
+#[cfg(feature = "arbitrary")]
+#[track_caller]
+fn fuzz_with<'a, T, F>(u: &mut Unstructured<'a>, check: F) -> arbitrary::Result<()>
+where
+    T: Arbitrary<'a> + fmt::Debug,
+    F: Fn([&T; 3]) -> Result<(), Law>,
+{
+    let values = <[T; 3]>::arbitrary(u)?;
+    let [a, b, c] = &values;
+    if let Err(law) = check([a, b, c]) {
+        panic!("{}", LawViolation { law, values })
+    }
+    Ok(())
+}
+
+/// Defines a `#[test]` function that asserts the [`ArbitraryOrd`] laws for a `proptest` strategy.
+///
+/// Add `hash` after the strategy to also check consistency with `Hash`.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+///
+/// ordered::arbitrary_ord_laws!(u32_laws, any::<u32>());
+/// ordered::arbitrary_ord_laws!(string_laws, any::<String>(), hash);
+/// ```
+#[cfg(feature = "proptest")]
+#[macro_export]
+macro_rules! arbitrary_ord_laws {
+    ($name:ident, $strategy:expr) => {
+        #[test]
+        fn $name() { $crate::laws::assert_laws($strategy) }
+    };
+    ($name:ident, $strategy:expr, hash) => {
+        #[test]
+        fn $name() { $crate::laws::assert_laws_with_hash($strategy) }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    #[cfg(feature = "proptest")]
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Compares only the lower byte, which is inconsistent with `PartialEq`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct LowByte(u32);
+
+    impl ArbitraryOrd for LowByte {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { (self.0 & 0xff).cmp(&(other.0 & 0xff)) }
+    }
+
+    /// Claims every value is less than every other value.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct AlwaysLess(u8);
+
+    impl ArbitraryOrd for AlwaysLess {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            if self == other {
+                Ordering::Equal
+            } else {
+                Ordering::Less
+            }
+        }
+    }
+
+    /// Rock, paper, scissors.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Hand(u8);
+
+    impl ArbitraryOrd for Hand {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            match (self.0 + 3 - other.0) % 3 {
+                0 => Ordering::Equal,
+                1 => Ordering::Greater,
+                _ => Ordering::Less,
+            }
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for Hand {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            u.int_in_range(0..=2).map(Hand)
+        }
+    }
+
+    /// Equal ignoring case but hashes the original bytes.
+    #[allow(clippy::derived_hash_with_manual_eq)] // Deliberately inconsistent.
+    #[derive(Debug, Clone, Copy, Hash)]
+    struct Letter(u8);
+
+    impl PartialEq for Letter {
+        fn eq(&self, other: &Self) -> bool { self.0.eq_ignore_ascii_case(&other.0) }
+    }
+
+    impl ArbitraryOrd for Letter {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            self.0.to_ascii_lowercase().cmp(&other.0.to_ascii_lowercase())
+        }
+    }
+
+    #[test]
+    fn check_single_triples() {
+        assert_eq!(check_triple(&1, &2, &3), Ok(()));
+        assert_eq!(check_triple_with_hash("a", "b", "a"), Ok(()));
+        assert_eq!(
+            check_triple(&LowByte(1), &LowByte(257), &LowByte(2)),
+            Err(Law::ConsistentWithEq)
+        );
+        assert_eq!(
+            check_triple(&AlwaysLess(1), &AlwaysLess(2), &AlwaysLess(1)),
+            Err(Law::Antisymmetry)
+        );
+        assert_eq!(check_triple(&Hand(0), &Hand(1), &Hand(2)), Err(Law::Transitivity));
+        assert_eq!(check_triple(&Letter(b'a'), &Letter(b'A'), &Letter(b'b')), Ok(()));
+        assert_eq!(
+            check_triple_with_hash(&Letter(b'a'), &Letter(b'A'), &Letter(b'b')),
+            Err(Law::ConsistentWithHash)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn correct_impls_pass() {
+        assert_laws_with_hash(any::<u32>());
+        assert_laws_with_hash(any::<(bool, i8)>());
+        assert_laws_with_hash(proptest::collection::vec(any::<u8>(), 0..4));
+        assert_laws((b'a'..=b'z').prop_map(Letter));
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn inconsistent_with_eq() {
+        // Few distinct upper bytes so that every run generates values with equal low bytes.
+        let err = check((0..4u32).prop_map(|high| LowByte(high << 8))).unwrap_err();
+        assert_eq!(err.law, Law::ConsistentWithEq);
+
+        // Two of the values differ only in the upper bytes.
+        let [a, b, c] = err.values;
+        let same_low_byte = |x: LowByte, y: LowByte| x != y && x.0 & 0xff == y.0 & 0xff;
+        assert!(same_low_byte(a, b) || same_low_byte(b, c) || same_low_byte(a, c));
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn not_antisymmetric() {
+        let err = check(any::<u8>().prop_map(AlwaysLess)).unwrap_err();
+        assert_eq!(err.law, Law::Antisymmetry);
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn not_transitive() {
+        let err = check((0_u8..3).prop_map(Hand)).unwrap_err();
+        assert_eq!(err.law, Law::Transitivity);
+
+        let [a, b, c] = err.values;
+        assert!(a != b && b != c && a != c);
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn inconsistent_with_hash() {
+        let letters = prop_oneof![Just(Letter(b'a')), Just(Letter(b'A'))];
+
+        assert!(check(letters.clone()).is_ok());
+        assert_eq!(check_with_hash(letters).unwrap_err().law, Law::ConsistentWithHash);
+    }
+
+    #[test]
+    fn violation_message() {
+        let violation = LawViolation { law: Law::Duality, values: [1, 2, 3] };
+        assert_eq!(
+            violation.to_string(),
+            "`ArbitraryOrd` violates duality of `Less` and `Greater` for 1, 2, 3"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    #[should_panic(expected = "violates transitivity")]
+    fn assert_panics() { assert_laws((0_u8..3).prop_map(Hand)); }
+
+    #[cfg(feature = "proptest")]
+    crate::arbitrary_ord_laws!(macro_defines_test, any::<i64>(), hash);
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn fuzz_correct_impls() {
+        let data: std::vec::Vec<u8> = (0..=255).collect();
+
+        for chunk in data.chunks(24) {
+            fuzz_with_hash::<(u32, i16)>(&mut Unstructured::new(chunk)).unwrap();
+            fuzz_with_hash::<Option<char>>(&mut Unstructured::new(chunk)).unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    #[should_panic(expected = "`ArbitraryOrd` violates transitivity for Hand(0), Hand(1), Hand(2)")]
+    fn fuzz_panics() { let _ = fuzz::<Hand>(&mut Unstructured::new(&[0, 1, 2])); }
+}
@@ -39,6 +39,10 @@
 //! }
 //! ```
 //!
+//! With the `derive` feature enabled `ArbitraryOrd` can also be derived using `#[derive(ArbitraryOrd)]`.
+//! With the `proptest` or `arbitrary` features enabled the `laws` module can be used to test, or
+//! fuzz, that `ArbitraryOrd` impls are total orders.
+//!
 //! [`examples/point.rs`]: <https://github.com/rust-bitcoin/rust-ordered/blob/master/examples/point.rs>
 
 #![no_std]
@@ -49,15 +53,98 @@
 #![warn(deprecated_in_future)]
 #![doc(test(attr(warn(unused))))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod array;
+#[cfg(feature = "alloc")]
+pub mod btree;
+pub mod checked;
+mod comparable;
+pub mod comparator;
+mod entries;
+pub mod float;
+mod impls;
+pub mod iter;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub mod laws;
+mod ordered_by;
+mod partial;
+#[cfg(feature = "serde")]
+mod serde;
+mod slice;
+pub mod sorted;
+#[cfg(feature = "alloc")]
+pub mod sorted_by;
+pub mod totalize;
+#[cfg(feature = "alloc")]
+pub mod vec_map;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
-use core::fmt;
+use core::mem::ManuallyDrop;
 use core::ops::{Deref, DerefMut};
+use core::{fmt, ptr};
+
+/// Derive macro generating an impl of the trait `ArbitraryOrd`.
+#[cfg(feature = "derive")]
+pub use ordered_derive::ArbitraryOrd;
+
+#[doc(inline)]
+pub use self::array::{ArrayMap, ArraySet};
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::btree::{OrderedMapExt, OrderedSetExt};
+#[doc(inline)]
+pub use self::checked::CheckedOrdered;
+pub use self::comparable::Comparable;
+#[doc(inline)]
+pub use self::comparator::Comparator;
+pub use self::impls::ByOrd;
+#[doc(inline)]
+pub use self::iter::ArbitraryIteratorExt;
+pub use self::ordered_by::{OrderFor, OrderedBy};
+pub use self::partial::{ArbitraryPartialOrd, PartiallyOrdered};
+pub use self::slice::ArbitrarySliceExt;
+#[cfg(feature = "alloc")]
+pub use self::slice::ArbitraryVecExt;
+#[doc(inline)]
+pub use self::sorted::SortedSlice;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::sorted::SortedVec;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::sorted_by::{SortedMapBy, SortedSetBy};
+#[doc(inline)]
+pub use self::totalize::Totalize;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::vec_map::OrderedVecMap;
 
 /// Trait for types that perform an arbitrary ordering.
 ///
-/// More specifically, this trait is for types that perform either a partial or
-/// total order but semantically it is nonsensical.
+/// More specifically, this trait is for types that perform a total order but semantically it is
+/// nonsensical. Types whose order is only partial should implement [`ArbitraryPartialOrd`] instead.
+///
+/// The trait is also implemented, by forwarding to `Ord`, for the primitive types that implement
+/// `Ord` and lexicographically for slices, arrays, tuples, `Option` and `Result` (and `Vec`,
+/// `VecDeque` and `String` with the `alloc` feature, `String` can also be compared to `str`). This
+/// allows containers of, and tuples that
+/// mix, arbitrarily ordered and naturally ordered types to be used with [`Ordered`]. References,
+/// `Pin` and, with the `alloc` feature, `Box`, `Rc`, `Arc` and `Cow` forward to the pointee.
+///
+/// `HashMap` and `HashSet` (with the `std` feature) and their `hashbrown` equivalents (with the
+/// `hashbrown` feature) are compared by sorting their contents first, so the ordering does not
+/// depend on the hasher or on insertion order.
 ///
 /// # Examples
 ///
@@ -82,7 +169,7 @@ use core::ops::{Deref, DerefMut};
 ///     }
 /// }
 /// ```
-pub trait ArbitraryOrd<Rhs = Self>: PartialEq<Rhs> {
+pub trait ArbitraryOrd<Rhs: ?Sized = Self>: PartialEq<Rhs> {
     /// Implements a meaningless, arbitrary ordering.
     fn arbitrary_cmp(&self, other: &Rhs) -> Ordering;
 }
@@ -117,10 +204,25 @@ pub trait ArbitraryOrd<Rhs = Self>: PartialEq<Rhs> {
 ///
 /// assert_eq!(*ordered, point); // Use `ops::Deref`.
 /// assert_eq!(&ordered.0, ordered.as_ref()); // Use the public inner field or `AsRef`.
+/// assert!(ordered < Point { x: 1, y: 0 }); // Compare directly against a bare `Point`.
+/// ```
+///
+/// `Ordered<T>` can be compared to `Ordered<U>` whenever `T: ArbitraryOrd<U>`:
+///
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// # #[cfg(feature = "alloc")] {
+/// use ordered::Ordered;
+///
+/// let owned = Ordered(String::from("abc"));
+/// let borrowed: &Ordered<str> = Ordered::from_ref("abd");
+///
+/// assert!(owned < *borrowed);
+/// assert!(*borrowed > owned);
+/// # }
+/// ```
+#[derive(Debug, Clone, Hash)]
 #[repr(transparent)]
-pub struct Ordered<T>(pub T);
+pub struct Ordered<T: ?Sized>(pub T);
 
 impl<T: Copy> Copy for Ordered<T> {}
 
@@ -130,38 +232,161 @@ impl<T> Ordered<T> {
     /// The inner type is public so this function is never explicitly needed.
     pub const fn new(inner: T) -> Self { Self(inner) }
 
+    /// Returns the inner object.
+    ///
+    /// We also implement [`core::ops::Deref`] so this function is never explicitly needed.
+    #[deprecated(since = "0.3.0", note = "use `ops::Deref` instead")]
+    pub fn into_inner(self) -> T { self.0 }
+
+    /// Creates an `&[Ordered<T>]` from a slice without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_slice(slice: &[T]) -> &[Self] {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so `[Ordered<T>]` has the same layout as `[T]`.
+        unsafe { &*(slice as *const [T] as *const [Self]) }
+    }
+
+    /// Creates an `&mut [Ordered<T>]` from a mutable slice without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_mut_slice(slice: &mut [T]) -> &mut [Self] {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so `[Ordered<T>]` has the same layout as `[T]`.
+        unsafe { &mut *(slice as *mut [T] as *mut [Self]) }
+    }
+
+    /// Returns the inner slice of an `&[Ordered<T>]` without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn inner_slice(slice: &[Self]) -> &[T] {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so `[Ordered<T>]` has the same layout as `[T]`.
+        unsafe { &*(slice as *const [Self] as *const [T]) }
+    }
+
+    /// Returns the inner slice of an `&mut [Ordered<T>]` without copying.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn inner_mut_slice(slice: &mut [Self]) -> &mut [T] {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so `[Ordered<T>]` has the same layout as `[T]`.
+        unsafe { &mut *(slice as *mut [Self] as *mut [T]) }
+    }
+
+    /// Creates a `Vec<Ordered<T>>` from a vector without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn from_vec(vec: Vec<T>) -> Vec<Self> {
+        let mut vec = ManuallyDrop::new(vec);
+        let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+        // SAFETY: `Ordered<T>` has the same size and alignment as `T` and the original vector is
+        // never dropped.
+        unsafe { Vec::from_raw_parts(ptr.cast::<Self>(), len, cap) }
+    }
+
+    /// Returns the inner vector of a `Vec<Ordered<T>>` without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_vec(vec: Vec<Self>) -> Vec<T> {
+        let mut vec = ManuallyDrop::new(vec);
+        let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+        // SAFETY: `Ordered<T>` has the same size and alignment as `T` and the original vector is
+        // never dropped.
+        unsafe { Vec::from_raw_parts(ptr.cast::<T>(), len, cap) }
+    }
+
+    /// Creates an array of `Ordered<T>` from an array without converting each element.
+    pub fn from_array<const N: usize>(array: [T; N]) -> [Self; N] {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so `[Ordered<T>; N]` has the same layout as
+        // `[T; N]`, and the original array is never dropped. `transmute` cannot be used because
+        // the size of an array of generic length is not known to it.
+        unsafe { ptr::read(ptr::addr_of!(*array).cast::<[Self; N]>()) }
+    }
+
+    /// Returns the inner array of an array of `Ordered<T>` without converting each element.
+    pub fn into_array<const N: usize>(array: [Self; N]) -> [T; N] {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so `[T; N]` has the same layout as
+        // `[Ordered<T>; N]`, and the original array is never dropped.
+        unsafe { ptr::read(ptr::addr_of!(*array).cast::<[T; N]>()) }
+    }
+}
+
+impl<T: ?Sized> Ordered<T> {
     /// Creates an `Ordered<T>` from a reference.
     ///
     /// This allows: `let found = map.get(Ordered::from_ref(&a));`
+    ///
+    /// `T` may be unsized, so a `&[T]` or a `&str` can be viewed as an `&Ordered<[T]>` or an
+    /// `&Ordered<str>` without allocating.
     #[allow(clippy::ptr_as_ptr)]
-    pub fn from_ref(value: &T) -> &Self { unsafe { &*(value as *const _ as *const Self) } }
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` over `T`.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Creates an `Ordered<T>` from a mutable reference.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_mut(value: &mut T) -> &mut Self {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` over `T`.
+        unsafe { &mut *(value as *mut T as *mut Self) }
+    }
+
+    /// Creates a `Box<Ordered<T>>` from a box without copying or reallocating.
+    ///
+    /// `T` may be unsized, so a `Box<[T]>` can be turned into a `Box<Ordered<[T]>>`.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::ptr_as_ptr)]
+    #[must_use]
+    pub fn from_box(boxed: Box<T>) -> Box<Self> {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so the layout and pointer metadata are the
+        // same as `T`.
+        unsafe { Box::from_raw(Box::into_raw(boxed) as *mut Self) }
+    }
+
+    /// Returns the inner box of a `Box<Ordered<T>>` without copying or reallocating.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::ptr_as_ptr)]
+    #[must_use]
+    pub fn into_box(boxed: Box<Self>) -> Box<T> {
+        // SAFETY: `Ordered<T>` is `repr(transparent)` so the layout and pointer metadata are the
+        // same as `T`.
+        unsafe { Box::from_raw(Box::into_raw(boxed) as *mut T) }
+    }
 
     /// Returns a reference to the inner object.
     ///
     /// We also implement [`core::borrow::Borrow`] so this function is never explicitly needed.
     #[deprecated(since = "0.3.0", note = "use `ops::Deref` instead")]
     pub const fn as_inner(&self) -> &T { &self.0 }
-
-    /// Returns the inner object.
-    ///
-    /// We also implement [`core::ops::Deref`] so this function is never explicitly needed.
-    #[deprecated(since = "0.3.0", note = "use `ops::Deref` instead")]
-    pub fn into_inner(self) -> T { self.0 }
 }
 
-impl<T: ArbitraryOrd> ArbitraryOrd for &T {
+impl<T: ArbitraryOrd + ?Sized> ArbitraryOrd for &T {
     fn arbitrary_cmp(&self, other: &Self) -> Ordering { (*self).arbitrary_cmp(other) }
 }
 
-impl<T: ArbitraryOrd> PartialOrd for Ordered<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some((*self).arbitrary_cmp(other)) }
+impl<T: PartialEq<U> + ?Sized, U: ?Sized> PartialEq<Ordered<U>> for Ordered<T> {
+    fn eq(&self, other: &Ordered<U>) -> bool { self.0 == other.0 }
 }
 
-impl<T: ArbitraryOrd + Eq> Ord for Ordered<T> {
+impl<T: Eq + ?Sized> Eq for Ordered<T> {}
+
+impl<T: ArbitraryOrd<U> + ?Sized, U: ?Sized> PartialOrd<Ordered<U>> for Ordered<T> {
+    fn partial_cmp(&self, other: &Ordered<U>) -> Option<Ordering> {
+        Some(self.0.arbitrary_cmp(&other.0))
+    }
+}
+
+// Comparisons against a bare `T`. These can not overlap with the impls above because
+// `Ordered<U>` never implements `ArbitraryOrd`.
+
+impl<T: ArbitraryOrd + ?Sized> PartialEq<T> for Ordered<T> {
+    fn eq(&self, other: &T) -> bool { self.0 == *other }
+}
+
+impl<T: ArbitraryOrd + ?Sized> PartialOrd<T> for Ordered<T> {
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> { Some(self.0.arbitrary_cmp(other)) }
+}
+
+impl<T: ArbitraryOrd + Eq + ?Sized> Ord for Ordered<T> {
     fn cmp(&self, other: &Self) -> Ordering { (*self).arbitrary_cmp(other) }
 }
 
-impl<T: fmt::Display> fmt::Display for Ordered<T> {
+impl<T: fmt::Display + ?Sized> fmt::Display for Ordered<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.0, f) }
 }
 
@@ -169,32 +394,68 @@ impl<T> From<T> for Ordered<T> {
     fn from(inner: T) -> Self { Self(inner) }
 }
 
-impl<T> AsRef<T> for Ordered<T> {
+impl<T: ?Sized> AsRef<T> for Ordered<T> {
     fn as_ref(&self) -> &T { &self.0 }
 }
 
-impl<T> AsMut<T> for Ordered<T> {
+impl<T: ?Sized> AsMut<T> for Ordered<T> {
     fn as_mut(&mut self) -> &mut T { &mut self.0 }
 }
 
-impl<T> Borrow<T> for Ordered<T> {
+impl<T: ?Sized> Borrow<T> for Ordered<T> {
     fn borrow(&self) -> &T { &self.0 }
 }
 
-impl<T> BorrowMut<T> for Ordered<T> {
+impl<T: ?Sized> BorrowMut<T> for Ordered<T> {
     fn borrow_mut(&mut self) -> &mut T { &mut self.0 }
 }
 
-impl<T> Deref for Ordered<T> {
+impl<T: ?Sized> Deref for Ordered<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target { &self.0 }
 }
 
-impl<T> DerefMut for Ordered<T> {
+impl<T: ?Sized> DerefMut for Ordered<T> {
     fn deref_mut(&mut self) -> &mut T { &mut self.0 }
 }
 
+/// Not public API, used by the code generated by `#[derive(ArbitraryOrd)]`.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    use core::cmp::Ordering;
+
+    use crate::ArbitraryOrd;
+
+    /// Wraps a reference to a field so that method resolution picks the best available ordering.
+    ///
+    /// `ViaArbitraryOrd` is implemented on `Field` and `ViaOrd` on `&Field`. Calling
+    /// `(&Field(a)).__ordered_cmp(b)` therefore uses `ArbitraryOrd` if the field type implements it
+    /// and only falls back to `Ord` by auto-referencing if it does not.
+    pub struct Field<'a, T: ?Sized>(pub &'a T);
+
+    /// Compares a field using `ArbitraryOrd`.
+    pub trait ViaArbitraryOrd<T: ?Sized> {
+        /// Compares the wrapped field to `other`.
+        fn __ordered_cmp(&self, other: &T) -> Ordering;
+    }
+
+    impl<T: ArbitraryOrd + ?Sized> ViaArbitraryOrd<T> for Field<'_, T> {
+        fn __ordered_cmp(&self, other: &T) -> Ordering { self.0.arbitrary_cmp(other) }
+    }
+
+    /// Compares a field using `Ord`.
+    pub trait ViaOrd<T: ?Sized> {
+        /// Compares the wrapped field to `other`.
+        fn __ordered_cmp(&self, other: &T) -> Ordering;
+    }
+
+    impl<T: Ord + ?Sized> ViaOrd<T> for &Field<'_, T> {
+        fn __ordered_cmp(&self, other: &T) -> Ordering { self.0.cmp(other) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,6 +492,131 @@ mod tests {
         assert!(Ordered::from_ref(&a) < Ordered::from_ref(&b));
     }
 
+    #[test]
+    fn can_compare_unsized() {
+        let a = [Point::new(2, 3), Point::new(5, 7)];
+        let b = [Point::new(2, 3), Point::new(5, 8)];
+
+        assert!(Ordered::from_ref(&a[..]) < Ordered::from_ref(&b[..]));
+        assert!(Ordered::from_ref("abc") < Ordered::from_ref("abd"));
+        assert_eq!(&Ordered::from_ref("abc").0, "abc");
+    }
+
+    #[test]
+    fn can_look_up_unsized_keys() {
+        extern crate std;
+        use std::collections::BTreeMap;
+
+        let a = [Point::new(2, 3), Point::new(5, 7)];
+        let b = [Point::new(1, 1)];
+
+        let mut map: BTreeMap<&Ordered<[Point]>, u32> = BTreeMap::new();
+        map.insert(Ordered::from_ref(&a), 1);
+        map.insert(Ordered::from_ref(&b), 2);
+
+        let key = [Point::new(1, 1)];
+        assert_eq!(map.get(Ordered::from_ref(&key[..])), Some(&2));
+    }
+
+    #[test]
+    fn can_project_references_and_slices() {
+        let mut a = Point::new(2, 3);
+        Ordered::from_mut(&mut a).0.x = 4;
+        assert_eq!(a, Point::new(4, 3));
+
+        let mut points = [Point::new(5, 7), Point::new(2, 3)];
+        let ordered = Ordered::from_slice(&points);
+        assert!(ordered[1] < ordered[0]);
+        assert_eq!(Ordered::inner_slice(ordered), &points);
+
+        let ordered = Ordered::from_mut_slice(&mut points);
+        ordered.sort();
+        assert_eq!(Ordered::inner_mut_slice(ordered), &[Point::new(2, 3), Point::new(5, 7)]);
+    }
+
+    #[test]
+    fn can_project_arrays() {
+        let points = [Point::new(5, 7), Point::new(2, 3)];
+
+        let mut ordered = Ordered::from_array(points);
+        ordered.sort();
+
+        assert_eq!(Ordered::into_array(ordered), [Point::new(2, 3), Point::new(5, 7)]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn can_project_vecs_and_boxes() {
+        use alloc::vec;
+
+        let points = vec![Point::new(5, 7), Point::new(2, 3)];
+        let ptr = points.as_ptr();
+
+        let mut ordered = Ordered::from_vec(points);
+        assert_eq!(ordered.as_ptr().cast::<Point>(), ptr);
+        ordered.sort();
+        ordered.push(Ordered(Point::new(9, 9)));
+        let points = Ordered::into_vec(ordered);
+        assert_eq!(points, [Point::new(2, 3), Point::new(5, 7), Point::new(9, 9)]);
+
+        let boxed = Ordered::from_box(Box::new(Point::new(1, 2)));
+        assert_eq!(*Ordered::into_box(boxed), Point::new(1, 2));
+
+        let slice: Box<[Point]> = vec![Point::new(5, 7), Point::new(2, 3)].into_boxed_slice();
+        let mut slice = Ordered::from_box(slice);
+        Ordered::from_mut_slice(&mut slice.0).sort();
+        assert_eq!(&*Ordered::into_box(slice), &[Point::new(2, 3), Point::new(5, 7)]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn can_look_up_boxed_unsized_keys() {
+        extern crate std;
+        use std::collections::BTreeMap;
+
+        let a: Box<[Point]> = Box::new([Point::new(2, 3), Point::new(5, 7)]);
+        let b: Box<[Point]> = Box::new([Point::new(1, 1)]);
+
+        let mut map: BTreeMap<Box<Ordered<[Point]>>, u32> = BTreeMap::new();
+        map.insert(Ordered::from_box(a), 1);
+        map.insert(Ordered::from_box(b), 2);
+
+        let key = [Point::new(1, 1)];
+        assert_eq!(map.get(Ordered::from_ref(&key[..])), Some(&2));
+    }
+
+    #[test]
+    fn can_compare_heterogeneous() {
+        /// A type that compares with `Point` by value.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Wrapper(Point);
+
+        impl PartialEq<Point> for Wrapper {
+            fn eq(&self, other: &Point) -> bool { self.0 == *other }
+        }
+
+        impl ArbitraryOrd<Point> for Wrapper {
+            fn arbitrary_cmp(&self, other: &Point) -> Ordering { self.0.arbitrary_cmp(other) }
+        }
+
+        let a = Ordered(Wrapper(Point::new(2, 3)));
+        let b = Ordered(Point::new(5, 7));
+
+        assert!(a < b);
+        assert!(a != b);
+        assert!(a == Ordered(Point::new(2, 3)));
+    }
+
+    #[test]
+    fn can_compare_with_bare_value() {
+        let a = Ordered(Point::new(2, 3));
+
+        assert!(a < Point::new(5, 7));
+        assert!(a > Point::new(2, 2));
+        assert!(a == Point::new(2, 3));
+        assert!(*Ordered::from_ref(&[Point::new(1, 1)][..]) < [Point::new(1, 2)][..]);
+    }
+
     #[test]
     fn can_compare_with_reference() {
         let a = Point::new(2, 3);
@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides [`OrderedBy`], a wrapper that is ordered by an order tag.
+
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+use crate::comparator::ArbitraryOrder;
+use crate::ArbitraryOrd;
+
+/// An order on `T` selected at the type level.
+///
+/// Implementors are usually zero-sized tag types, this allows one type to have several named
+/// orderings. The ordering must be total and should agree with the `PartialEq` impl of `T`, in the
+/// same way as [`ArbitraryOrd`].
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::OrderFor;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// /// Orders points x-major.
+/// enum ByX {}
+///
+/// impl OrderFor<Point> for ByX {
+///     fn order_cmp(a: &Point, b: &Point) -> Ordering { (a.x, a.y).cmp(&(b.x, b.y)) }
+/// }
+/// ```
+pub trait OrderFor<T: ?Sized> {
+    /// Compares `a` to `b`.
+    fn order_cmp(a: &T, b: &T) -> Ordering;
+}
+
+impl<T: ArbitraryOrd + ?Sized> OrderFor<T> for ArbitraryOrder<T> {
+    fn order_cmp(a: &T, b: &T) -> Ordering { a.arbitrary_cmp(b) }
+}
+
+/// A wrapper type that implements `PartialOrd` and `Ord` using the order tag `O`.
+///
+/// This is the same as [`Ordered`](crate::Ordered) except the ordering comes from `O` instead of
+/// from the [`ArbitraryOrd`] impl of `T`. The order tag is stored first so that `T` can be unsized,
+/// the wrapped value is the second field.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use std::collections::BTreeMap;
+/// use ordered::{OrderFor, OrderedBy};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// enum ByX {}
+///
+/// impl OrderFor<Point> for ByX {
+///     fn order_cmp(a: &Point, b: &Point) -> Ordering { (a.x, a.y).cmp(&(b.x, b.y)) }
+/// }
+///
+/// enum ByY {}
+///
+/// impl OrderFor<Point> for ByY {
+///     fn order_cmp(a: &Point, b: &Point) -> Ordering { (a.y, a.x).cmp(&(b.y, b.x)) }
+/// }
+///
+/// let a = Point { x: 1, y: 2 };
+/// let b = Point { x: 2, y: 1 };
+///
+/// let mut by_x = BTreeMap::new();
+/// by_x.insert(OrderedBy::<_, ByX>::new(a), "a");
+/// by_x.insert(OrderedBy::new(b), "b");
+///
+/// let mut by_y = BTreeMap::new();
+/// by_y.insert(OrderedBy::<_, ByY>::new(a), "a");
+/// by_y.insert(OrderedBy::new(b), "b");
+///
+/// assert_eq!(by_x.values().copied().collect::<Vec<_>>(), ["a", "b"]);
+/// assert_eq!(by_y.values().copied().collect::<Vec<_>>(), ["b", "a"]);
+/// assert_eq!(by_y.get(OrderedBy::from_ref(&a)), Some(&"a"));
+/// ```
+#[repr(transparent)]
+pub struct OrderedBy<T: ?Sized, O>(PhantomData<fn() -> O>, pub T);
+
+impl<T, O> OrderedBy<T, O> {
+    /// Creates a new wrapped ordered type.
+    pub const fn new(inner: T) -> Self { Self(PhantomData, inner) }
+
+    /// Returns the inner object.
+    pub fn into_inner(self) -> T { self.1 }
+}
+
+impl<T: ?Sized, O> OrderedBy<T, O> {
+    /// Creates an `OrderedBy<T, O>` from a reference.
+    ///
+    /// This allows: `let found = map.get(OrderedBy::from_ref(&a));`
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: `OrderedBy` is `repr(transparent)` over `T` and `PhantomData` is zero-sized, so
+        // the pointer metadata of `T` is also valid for `Self`.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T: ?Sized, O: OrderFor<T>> PartialOrd for OrderedBy<T, O>
+where
+    T: PartialEq,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(O::order_cmp(&self.1, &other.1))
+    }
+}
+
+impl<T: ?Sized, O: OrderFor<T>> Ord for OrderedBy<T, O>
+where
+    T: Eq,
+{
+    fn cmp(&self, other: &Self) -> Ordering { O::order_cmp(&self.1, &other.1) }
+}
+
+impl<T: PartialEq + ?Sized, O> PartialEq for OrderedBy<T, O> {
+    fn eq(&self, other: &Self) -> bool { self.1 == other.1 }
+}
+
+impl<T: Eq + ?Sized, O> Eq for OrderedBy<T, O> {}
+
+impl<T: Hash + ?Sized, O> Hash for OrderedBy<T, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.1.hash(state) }
+}
+
+impl<T: Clone, O> Clone for OrderedBy<T, O> {
+    fn clone(&self) -> Self { Self::new(self.1.clone()) }
+}
+
+impl<T: Copy, O> Copy for OrderedBy<T, O> {}
+
+impl<T: fmt::Debug + ?Sized, O> fmt::Debug for OrderedBy<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OrderedBy").field(&&self.1).finish()
+    }
+}
+
+impl<T: fmt::Display + ?Sized, O> fmt::Display for OrderedBy<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.1, f) }
+}
+
+impl<T, O> From<T> for OrderedBy<T, O> {
+    fn from(inner: T) -> Self { Self::new(inner) }
+}
+
+impl<T: ?Sized, O> AsRef<T> for OrderedBy<T, O> {
+    fn as_ref(&self) -> &T { &self.1 }
+}
+
+impl<T: ?Sized, O> AsMut<T> for OrderedBy<T, O> {
+    fn as_mut(&mut self) -> &mut T { &mut self.1 }
+}
+
+impl<T: ?Sized, O> Borrow<T> for OrderedBy<T, O> {
+    fn borrow(&self) -> &T { &self.1 }
+}
+
+impl<T: ?Sized, O> BorrowMut<T> for OrderedBy<T, O> {
+    fn borrow_mut(&mut self) -> &mut T { &mut self.1 }
+}
+
+impl<T: ?Sized, O> Deref for OrderedBy<T, O> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target { &self.1 }
+}
+
+impl<T: ?Sized, O> DerefMut for OrderedBy<T, O> {
+    fn deref_mut(&mut self) -> &mut T { &mut self.1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    impl Point {
+        fn new(x: u32, y: u32) -> Self { Point { x, y } }
+    }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { ByY::order_cmp(self, other) }
+    }
+
+    enum ByX {}
+
+    impl OrderFor<Point> for ByX {
+        fn order_cmp(a: &Point, b: &Point) -> Ordering { (a.x, a.y).cmp(&(b.x, b.y)) }
+    }
+
+    impl OrderFor<[Point]> for ByX {
+        fn order_cmp(a: &[Point], b: &[Point]) -> Ordering {
+            a.iter().map(|p| (p.x, p.y)).cmp(b.iter().map(|p| (p.x, p.y)))
+        }
+    }
+
+    enum ByY {}
+
+    impl OrderFor<Point> for ByY {
+        fn order_cmp(a: &Point, b: &Point) -> Ordering { (a.y, a.x).cmp(&(b.y, b.x)) }
+    }
+
+    #[test]
+    fn tags_select_order() {
+        let a = Point::new(1, 2);
+        let b = Point::new(2, 1);
+
+        assert!(OrderedBy::<_, ByX>::new(a) < OrderedBy::new(b));
+        assert!(OrderedBy::<_, ByY>::new(a) > OrderedBy::new(b));
+    }
+
+    #[test]
+    fn can_compare_with_from_ref() {
+        let a = Point::new(1, 2);
+        let b = Point::new(2, 1);
+
+        assert!(OrderedBy::<_, ByX>::from_ref(&a) < OrderedBy::from_ref(&b));
+    }
+
+    #[test]
+    fn unsized_from_ref() {
+        let a = [Point::new(1, 2), Point::new(3, 4)];
+        let b = [Point::new(2, 1)];
+
+        assert!(OrderedBy::<[Point], ByX>::from_ref(&a) < OrderedBy::from_ref(&b));
+        assert!(OrderedBy::<[Point], ArbitraryOrder<_>>::from_ref(&a) > OrderedBy::from_ref(&b));
+        assert_eq!(&OrderedBy::<str, ArbitraryOrder<str>>::from_ref("abc").1, "abc");
+    }
+
+    #[test]
+    fn arbitrary_order_tag() {
+        let a = Point::new(1, 2);
+        let b = Point::new(2, 1);
+
+        assert!(OrderedBy::<_, ArbitraryOrder<_>>::new(a) > OrderedBy::new(b));
+    }
+
+    #[test]
+    fn is_transparent() {
+        use core::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<OrderedBy<Point, ByX>>(), size_of::<Point>());
+        assert_eq!(align_of::<OrderedBy<Point, ByX>>(), align_of::<Point>());
+    }
+
+    #[test]
+    fn send_sync_do_not_depend_on_tag() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+
+        // A tag that is neither `Send` nor `Sync`.
+        struct Tag(PhantomData<*const ()>);
+
+        assert_send::<OrderedBy<Point, Tag>>();
+        assert_sync::<OrderedBy<Point, Tag>>();
+    }
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides [`ArbitraryPartialOrd`] and [`PartiallyOrdered`], for types that only have a partial
+//! order.
+
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::ArbitraryOrd;
+
+/// Trait for types that have a partial order which is not meaningful enough to implement
+/// `PartialOrd` directly.
+///
+/// This is the partial counterpart of [`ArbitraryOrd`], some pairs of values may be incomparable in
+/// which case `arbitrary_partial_cmp` returns `None`. Every `ArbitraryOrd` type is also
+/// `ArbitraryPartialOrd`.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{ArbitraryPartialOrd, PartiallyOrdered};
+///
+/// /// A transaction's fee and weight.
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct FeeWeight {
+///     fee: u64,
+///     weight: u64,
+/// }
+///
+/// /// One pair dominates another if it pays at least as much fee for at most as much weight.
+/// impl ArbitraryPartialOrd for FeeWeight {
+///     fn arbitrary_partial_cmp(&self, other: &Self) -> Option<Ordering> {
+///         match (self.fee.cmp(&other.fee), other.weight.cmp(&self.weight)) {
+///             (a, b) if a == b => Some(a),
+///             (Ordering::Equal, ord) | (ord, Ordering::Equal) => Some(ord),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let cheap = PartiallyOrdered(FeeWeight { fee: 100, weight: 400 });
+/// let better = PartiallyOrdered(FeeWeight { fee: 200, weight: 400 });
+/// let heavy = PartiallyOrdered(FeeWeight { fee: 300, weight: 800 });
+///
+/// assert!(cheap < better);
+/// assert_eq!(better.partial_cmp(&heavy), None);
+/// ```
+pub trait ArbitraryPartialOrd<Rhs: ?Sized = Self>: PartialEq<Rhs> {
+    /// Implements a meaningless, arbitrary partial ordering.
+    fn arbitrary_partial_cmp(&self, other: &Rhs) -> Option<Ordering>;
+}
+
+impl<T: ArbitraryOrd<Rhs> + ?Sized, Rhs: ?Sized> ArbitraryPartialOrd<Rhs> for T {
+    fn arbitrary_partial_cmp(&self, other: &Rhs) -> Option<Ordering> {
+        Some(self.arbitrary_cmp(other))
+    }
+}
+
+/// A wrapper type that implements `PartialOrd` using [`ArbitraryPartialOrd`].
+///
+/// This is the same as [`Ordered`](crate::Ordered) except it does not implement `Ord`, so it can
+/// be used with types that have incomparable values.
+#[derive(Debug, Clone, Hash)]
+#[repr(transparent)]
+pub struct PartiallyOrdered<T: ?Sized>(pub T);
+
+impl<T: Copy> Copy for PartiallyOrdered<T> {}
+
+impl<T> PartiallyOrdered<T> {
+    /// Creates a new wrapped partially ordered type.
+    ///
+    /// The inner type is public so this function is never explicitly needed.
+    pub const fn new(inner: T) -> Self { Self(inner) }
+}
+
+impl<T: ?Sized> PartiallyOrdered<T> {
+    /// Creates a `PartiallyOrdered<T>` from a reference.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: `PartiallyOrdered` is `repr(transparent)` over `T`.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+}
+
+impl<T: PartialEq<U> + ?Sized, U: ?Sized> PartialEq<PartiallyOrdered<U>> for PartiallyOrdered<T> {
+    fn eq(&self, other: &PartiallyOrdered<U>) -> bool { self.0 == other.0 }
+}
+
+impl<T: Eq + ?Sized> Eq for PartiallyOrdered<T> {}
+
+impl<T, U> PartialOrd<PartiallyOrdered<U>> for PartiallyOrdered<T>
+where
+    T: ArbitraryPartialOrd<U> + ?Sized,
+    U: ?Sized,
+{
+    fn partial_cmp(&self, other: &PartiallyOrdered<U>) -> Option<Ordering> {
+        self.0.arbitrary_partial_cmp(&other.0)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for PartiallyOrdered<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.0, f) }
+}
+
+impl<T> From<T> for PartiallyOrdered<T> {
+    fn from(inner: T) -> Self { Self(inner) }
+}
+
+impl<T: ?Sized> AsRef<T> for PartiallyOrdered<T> {
+    fn as_ref(&self) -> &T { &self.0 }
+}
+
+impl<T: ?Sized> AsMut<T> for PartiallyOrdered<T> {
+    fn as_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+impl<T: ?Sized> Borrow<T> for PartiallyOrdered<T> {
+    fn borrow(&self) -> &T { &self.0 }
+}
+
+impl<T: ?Sized> BorrowMut<T> for PartiallyOrdered<T> {
+    fn borrow_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+impl<T: ?Sized> Deref for PartiallyOrdered<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T: ?Sized> DerefMut for PartiallyOrdered<T> {
+    fn deref_mut(&mut self) -> &mut T { &mut self.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ordered by dominance, `a <= b` iff both coordinates of `a` are less than or equal.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Pair(u32, u32);
+
+    impl ArbitraryPartialOrd for Pair {
+        fn arbitrary_partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            match (self.0.cmp(&other.0), self.1.cmp(&other.1)) {
+                (a, b) if a == b => Some(a),
+                (Ordering::Equal, ord) | (ord, Ordering::Equal) => Some(ord),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn incomparable_values() {
+        let a = PartiallyOrdered(Pair(1, 2));
+        let b = PartiallyOrdered(Pair(2, 1));
+
+        assert_eq!(a.partial_cmp(&b), None);
+        assert_eq!(b.partial_cmp(&a), None);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn comparable_values() {
+        let a = PartiallyOrdered(Pair(1, 2));
+
+        assert!(a < PartiallyOrdered(Pair(1, 3)));
+        assert!(a < PartiallyOrdered(Pair(2, 3)));
+        assert!(a >= PartiallyOrdered(Pair(0, 2)));
+        assert!(a <= *PartiallyOrdered::from_ref(&Pair(1, 2)));
+    }
+
+    #[test]
+    fn total_orders_are_partial_orders() {
+        assert_eq!(1_u32.arbitrary_partial_cmp(&2), Some(Ordering::Less));
+        assert!(PartiallyOrdered("abc") > PartiallyOrdered("ab"));
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Implements `Serialize` and `Deserialize` for `Ordered` and `CheckedOrdered`.
+//!
+//! Both are transparent, they serialize exactly like `T` and deserialize from anything `T`
+//! deserializes from.
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{CheckedOrdered, Ordered};
+
+impl<T: Serialize + ?Sized> Serialize for Ordered<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Ordered<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for CheckedOrdered<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CheckedOrdered<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
@@ -0,0 +1,325 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Provides [`ArbitrarySliceExt`] and [`ArbitraryVecExt`], for sorting and searching without
+//! wrapping values in [`Ordered`](crate::Ordered).
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::ArbitraryOrd;
+
+/// Extension trait for slices of [`ArbitraryOrd`] types.
+///
+/// Each method is the same as the `slice` method without the `arbitrary_` prefix, using the order
+/// defined by `ArbitraryOrd` instead of `Ord`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate, so that methods can be
+/// added without breaking other implementations.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{ArbitraryOrd, ArbitrarySliceExt};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// impl ArbitraryOrd for Point {
+///     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+///         (self.x, self.y).cmp(&(other.x, other.y))
+///     }
+/// }
+///
+/// let mut points = [Point { x: 5, y: 7 }, Point { x: 2, y: 3 }, Point { x: 5, y: 1 }];
+/// points.arbitrary_sort_unstable();
+///
+/// assert!(points.is_arbitrarily_sorted());
+/// assert_eq!(points.arbitrary_binary_search(&Point { x: 5, y: 1 }), Ok(1));
+/// ```
+pub trait ArbitrarySliceExt<T: ArbitraryOrd>: sealed::Sealed {
+    /// Sorts the slice, preserving the initial order of equal elements.
+    ///
+    /// With the `alloc` feature this is `slice::sort_by`, which allocates a buffer. Without it an
+    /// in-place merge sort is used, which does not allocate but takes `O(n log² n)` time instead of
+    /// `O(n log n)`.
+    fn arbitrary_sort(&mut self);
+
+    /// Sorts the slice, without preserving the initial order of equal elements.
+    fn arbitrary_sort_unstable(&mut self);
+
+    /// Binary searches a sorted slice for `x`.
+    ///
+    /// # Errors
+    ///
+    /// If `x` is not found, returns the index where it could be inserted to keep the slice sorted.
+    fn arbitrary_binary_search<Q: ?Sized>(&self, x: &Q) -> Result<usize, usize>
+    where
+        T: ArbitraryOrd<Q>;
+
+    /// Returns the index of the first element of a sorted slice that is not less than `x`.
+    ///
+    /// This is the position of the partition point of the predicate `elem < x`.
+    fn arbitrary_partition_point<Q: ?Sized>(&self, x: &Q) -> usize
+    where
+        T: ArbitraryOrd<Q>;
+
+    /// Returns `true` if the slice is sorted.
+    fn is_arbitrarily_sorted(&self) -> bool;
+
+    /// Reorders the slice so that the element at `index` is at its sorted position.
+    ///
+    /// Returns the elements before `index`, which are all less than or equal to it, the element at
+    /// `index`, and the elements after `index`, which are all greater than or equal to it.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= len()`.
+    fn arbitrary_select_nth(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T]);
+}
+
+impl<T: ArbitraryOrd> ArbitrarySliceExt<T> for [T] {
+    #[cfg(feature = "alloc")]
+    fn arbitrary_sort(&mut self) { self.sort_by(T::arbitrary_cmp) }
+
+    #[cfg(not(feature = "alloc"))]
+    fn arbitrary_sort(&mut self) { stable_sort_in_place(self, &mut T::arbitrary_cmp) }
+
+    fn arbitrary_sort_unstable(&mut self) { self.sort_unstable_by(T::arbitrary_cmp) }
+
+    fn arbitrary_binary_search<Q: ?Sized>(&self, x: &Q) -> Result<usize, usize>
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        self.binary_search_by(|elem| elem.arbitrary_cmp(x))
+    }
+
+    fn arbitrary_partition_point<Q: ?Sized>(&self, x: &Q) -> usize
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        self.partition_point(|elem| elem.arbitrary_cmp(x) == Ordering::Less)
+    }
+
+    fn is_arbitrarily_sorted(&self) -> bool {
+        self.windows(2).all(|w| w[0].arbitrary_cmp(&w[1]) != Ordering::Greater)
+    }
+
+    fn arbitrary_select_nth(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T]) {
+        self.select_nth_unstable_by(index, T::arbitrary_cmp)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl<T> Sealed for [T] {}
+}
+
+/// Sorts `v` stably without allocating, by merging sorted halves in place with rotations.
+///
+/// Only swaps and rotates elements, so if `cmp` panics `v` is left as a permutation of its
+/// original elements.
+#[cfg(any(test, not(feature = "alloc")))]
+fn stable_sort_in_place<T, F>(v: &mut [T], cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    /// Slices up to this length are insertion sorted.
+    const INSERTION_LEN: usize = 16;
+
+    let len = v.len();
+    if len <= INSERTION_LEN {
+        for i in 1..len {
+            let mut j = i;
+            while j > 0 && cmp(&v[j - 1], &v[j]) == Ordering::Greater {
+                v.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+        return;
+    }
+
+    let mid = len / 2;
+    stable_sort_in_place(&mut v[..mid], cmp);
+    stable_sort_in_place(&mut v[mid..], cmp);
+    merge_in_place(v, mid, cmp);
+}
+
+/// Merges the sorted runs `v[..mid]` and `v[mid..]`, equal elements of the left run stay first.
+#[cfg(any(test, not(feature = "alloc")))]
+fn merge_in_place<T, F>(v: &mut [T], mid: usize, cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = v.len();
+    if mid == 0 || mid == len || cmp(&v[mid - 1], &v[mid]) != Ordering::Greater {
+        return;
+    }
+    if len == 2 {
+        v.swap(0, 1);
+        return;
+    }
+
+    // Split the longer run in half and find where its middle element splits the other run, then
+    // rotate the two inner parts past each other and merge both sides.
+    let (left_cut, right_cut) = if mid >= len - mid {
+        let left_cut = mid / 2;
+        let pivot = &v[left_cut];
+        (left_cut, mid + v[mid..].partition_point(|x| cmp(x, pivot) == Ordering::Less))
+    } else {
+        let right_cut = mid + (len - mid) / 2;
+        let pivot = &v[right_cut];
+        (v[..mid].partition_point(|x| cmp(x, pivot) != Ordering::Greater), right_cut)
+    };
+    v[left_cut..right_cut].rotate_left(mid - left_cut);
+
+    let new_mid = left_cut + (right_cut - mid);
+    merge_in_place(&mut v[..new_mid], left_cut, cmp);
+    merge_in_place(&mut v[new_mid..], right_cut - new_mid, cmp);
+}
+
+/// Extension trait for vectors of [`ArbitraryOrd`] types.
+#[cfg(feature = "alloc")]
+pub trait ArbitraryVecExt<T: ArbitraryOrd> {
+    /// Removes consecutive elements that compare `Equal`, keeping the first one.
+    ///
+    /// If the vector is sorted this removes all duplicates.
+    fn arbitrary_dedup(&mut self);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> ArbitraryVecExt<T> for Vec<T> {
+    fn arbitrary_dedup(&mut self) {
+        self.dedup_by(|a, b| T::arbitrary_cmp(a, b) == Ordering::Equal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    fn p(x: u32, y: u32) -> Point { Point { x, y } }
+
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+            (self.x, self.y).cmp(&(other.x, other.y))
+        }
+    }
+
+    /// Compares only the `x` of a point.
+    struct X(u32);
+
+    impl PartialEq<X> for Point {
+        fn eq(&self, other: &X) -> bool { self.x == other.0 }
+    }
+
+    impl ArbitraryOrd<X> for Point {
+        fn arbitrary_cmp(&self, other: &X) -> Ordering { self.x.cmp(&other.0) }
+    }
+
+    #[test]
+    fn sort_unstable() {
+        let mut points = [p(5, 7), p(2, 3), p(5, 1), p(0, 9)];
+        assert!(!points.is_arbitrarily_sorted());
+
+        points.arbitrary_sort_unstable();
+        assert_eq!(points, [p(0, 9), p(2, 3), p(5, 1), p(5, 7)]);
+        assert!(points.is_arbitrarily_sorted());
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        /// Compares only the `y` of the point.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct ByY(Point);
+
+        impl ArbitraryOrd for ByY {
+            fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.y.cmp(&other.0.y) }
+        }
+
+        let mut points = [ByY(p(3, 1)), ByY(p(1, 0)), ByY(p(2, 1)), ByY(p(0, 1))];
+        points.arbitrary_sort();
+        assert_eq!(points, [ByY(p(1, 0)), ByY(p(3, 1)), ByY(p(2, 1)), ByY(p(0, 1))]);
+    }
+
+    #[test]
+    fn in_place_sort_is_stable() {
+        // Sorted by the first element only, the second element records the original position.
+        let mut pairs = [(0_u32, 0_u32); 200];
+        for (i, pair) in (0..).zip(pairs.iter_mut()) {
+            *pair = ((i * 7919) % 13, i);
+        }
+
+        stable_sort_in_place(&mut pairs, &mut |a: &(u32, u32), b: &(u32, u32)| a.0.cmp(&b.0));
+        assert!(pairs.windows(2).all(|w| w[0] < w[1]));
+
+        let mut reversed = [0_u32; 100];
+        for (i, x) in (0..).zip(reversed.iter_mut()) {
+            *x = 100 - i;
+        }
+        stable_sort_in_place(&mut reversed, &mut u32::cmp);
+        assert!(reversed.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn binary_search() {
+        let points = [p(0, 9), p(2, 3), p(5, 1), p(5, 7)];
+
+        assert_eq!(points.arbitrary_binary_search(&p(2, 3)), Ok(1));
+        assert_eq!(points.arbitrary_binary_search(&p(3, 0)), Err(2));
+        assert_eq!(points.arbitrary_binary_search(&p(9, 0)), Err(4));
+        assert_eq!(points.arbitrary_binary_search(&X(2)), Ok(1));
+    }
+
+    #[test]
+    fn partition_point() {
+        let points = [p(0, 9), p(2, 3), p(5, 1), p(5, 7)];
+
+        assert_eq!(points.arbitrary_partition_point(&p(5, 1)), 2);
+        assert_eq!(points.arbitrary_partition_point(&X(5)), 2);
+        assert_eq!(points.arbitrary_partition_point(&X(6)), 4);
+        assert_eq!(points.arbitrary_partition_point(&p(0, 0)), 0);
+    }
+
+    #[test]
+    fn select_nth() {
+        let mut points = [p(5, 7), p(2, 3), p(5, 1), p(0, 9), p(1, 1)];
+
+        let (before, nth, after) = points.arbitrary_select_nth(2);
+        assert_eq!(*nth, p(2, 3));
+        assert!(before.iter().all(|q| q.arbitrary_cmp(&p(2, 3)) == Ordering::Less));
+        assert!(after.iter().all(|q| q.arbitrary_cmp(&p(2, 3)) == Ordering::Greater));
+    }
+
+    #[test]
+    fn sorted_edge_cases() {
+        let empty: [Point; 0] = [];
+        assert!(empty.is_arbitrarily_sorted());
+        assert!([p(1, 1)].is_arbitrarily_sorted());
+        assert!([p(1, 1), p(1, 1)].is_arbitrarily_sorted());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dedup() {
+        use alloc::vec;
+
+        let mut points = vec![p(0, 9), p(2, 3), p(2, 3), p(5, 1), p(2, 3)];
+        points.arbitrary_dedup();
+        assert_eq!(points, [p(0, 9), p(2, 3), p(5, 1), p(2, 3)]);
+
+        points.arbitrary_sort();
+        points.arbitrary_dedup();
+        assert_eq!(points, [p(0, 9), p(2, 3), p(5, 1)]);
+    }
+}
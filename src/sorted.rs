@@ -0,0 +1,489 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Collections that are kept sorted according to [`ArbitraryOrd`].
+//!
+//! [`SortedSlice`] is a borrowed view of a slice that is known to be sorted and `SortedVec` is
+//! its owned counterpart. Both allow elements that compare `Equal`, which are kept in the order
+//! they were inserted.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use ordered::sorted::{SortedSlice, SortedVec};
+//!
+//! let mut vec = SortedVec::from_unsorted(vec![5, 1, 3]);
+//! vec.insert(2);
+//!
+//! assert_eq!(vec.as_slice(), [1, 2, 3, 5]);
+//! assert!(vec.contains(&3));
+//! assert_eq!(vec.range(2..4).as_slice(), [2, 3]);
+//!
+//! assert!(SortedSlice::new(&[1, 2, 2]).is_ok());
+//! assert!(SortedSlice::new(&[2, 1]).is_err());
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::fmt;
+use core::ops::{Bound, Deref, RangeBounds};
+use core::slice;
+
+use crate::iter::{ArbitraryIteratorExt, UnsortedError};
+use crate::ArbitraryOrd;
+
+/// A slice that is sorted according to [`ArbitraryOrd`].
+///
+/// Dereferences to `[T]` so all the read only slice methods are available, methods that rely on
+/// the slice being sorted are provided directly.
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SortedSlice<T>([T]);
+
+impl<T> SortedSlice<T> {
+    /// Creates a `SortedSlice<T>` from a slice without checking it is sorted.
+    #[allow(clippy::ptr_as_ptr)]
+    fn from_slice_unchecked(slice: &[T]) -> &Self {
+        // SAFETY: `SortedSlice` is `repr(transparent)` over `[T]`.
+        unsafe { &*(slice as *const [T] as *const Self) }
+    }
+
+    /// Returns the elements as a slice.
+    pub const fn as_slice(&self) -> &[T] { &self.0 }
+}
+
+impl<T: ArbitraryOrd> SortedSlice<T> {
+    /// Creates a `SortedSlice<T>` from a slice.
+    ///
+    /// # Errors
+    ///
+    /// If `slice` is not sorted, the error holds the first two elements that are out of order.
+    pub fn new(slice: &[T]) -> Result<&Self, UnsortedError<&T>> {
+        match slice.iter().check_sorted().find_map(Result::err) {
+            Some(e) => Err(e),
+            None => Ok(Self::from_slice_unchecked(slice)),
+        }
+    }
+
+    /// Binary searches the slice for `x`.
+    ///
+    /// # Errors
+    ///
+    /// If `x` is not found, returns the index where it could be inserted to keep the slice sorted.
+    pub fn binary_search<Q: ?Sized>(&self, x: &Q) -> Result<usize, usize>
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        self.0.binary_search_by(|elem| elem.arbitrary_cmp(x))
+    }
+
+    /// Returns `true` if the slice contains an element that compares `Equal` to `x`.
+    pub fn contains<Q: ?Sized>(&self, x: &Q) -> bool
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        self.binary_search(x).is_ok()
+    }
+
+    /// Returns the sub-slice of elements within `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end.
+    pub fn range<Q: ?Sized, R: RangeBounds<Q>>(&self, range: R) -> &Self
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(x) => self.partition_point(x, |ord| ord == Ordering::Less),
+            Bound::Excluded(x) => self.partition_point(x, |ord| ord != Ordering::Greater),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => self.partition_point(x, |ord| ord != Ordering::Greater),
+            Bound::Excluded(x) => self.partition_point(x, |ord| ord == Ordering::Less),
+            Bound::Unbounded => self.0.len(),
+        };
+        assert!(start <= end, "range start is greater than range end in `SortedSlice::range`");
+        Self::from_slice_unchecked(&self.0[start..end])
+    }
+
+    /// Returns the index of the first element for which `pred(elem.arbitrary_cmp(x))` is false.
+    fn partition_point<Q: ?Sized>(&self, x: &Q, pred: impl Fn(Ordering) -> bool) -> usize
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        self.0.partition_point(|elem| pred(elem.arbitrary_cmp(x)))
+    }
+}
+
+impl<T> Deref for SortedSlice<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T> AsRef<[T]> for SortedSlice<T> {
+    fn as_ref(&self) -> &[T] { &self.0 }
+}
+
+impl<T> Default for &SortedSlice<T> {
+    fn default() -> Self { SortedSlice::from_slice_unchecked(&[]) }
+}
+
+impl<'a, T> IntoIterator for &'a SortedSlice<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.iter() }
+}
+
+impl<'a, T: ArbitraryOrd> TryFrom<&'a [T]> for &'a SortedSlice<T> {
+    type Error = UnsortedError<&'a T>;
+
+    fn try_from(slice: &'a [T]) -> Result<Self, Self::Error> { SortedSlice::new(slice) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd + Clone> ToOwned for SortedSlice<T> {
+    type Owned = SortedVec<T>;
+
+    fn to_owned(&self) -> Self::Owned { SortedVec(self.0.to_vec()) }
+}
+
+/// A vector that is kept sorted according to [`ArbitraryOrd`].
+///
+/// Dereferences to [`SortedSlice`] for searching, and through it to `[T]`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SortedVec<T>(Vec<T>);
+
+#[cfg(feature = "alloc")]
+impl<T> SortedVec<T> {
+    /// Creates a new empty `SortedVec<T>`.
+    #[must_use]
+    pub const fn new() -> Self { Self(Vec::new()) }
+
+    /// Creates a new empty `SortedVec<T>` with at least the specified capacity.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self { Self(Vec::with_capacity(capacity)) }
+
+    /// Returns the elements as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] { &self.0 }
+
+    /// Returns the elements as a sorted slice.
+    #[must_use]
+    pub fn as_sorted_slice(&self) -> &SortedSlice<T> { SortedSlice::from_slice_unchecked(&self.0) }
+
+    /// Returns the inner vector.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> { self.0 }
+
+    /// Removes and returns the last, greatest, element.
+    pub fn pop(&mut self) -> Option<T> { self.0.pop() }
+
+    /// Removes all elements.
+    pub fn clear(&mut self) { self.0.clear() }
+
+    /// Retains only the elements for which `f` returns `true`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) { self.0.retain(f) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> SortedVec<T> {
+    /// Creates a `SortedVec<T>` by sorting `vec`.
+    ///
+    /// The sort is stable, elements that compare `Equal` keep their order.
+    pub fn from_unsorted(mut vec: Vec<T>) -> Self {
+        vec.sort_by(T::arbitrary_cmp);
+        Self(vec)
+    }
+
+    /// Creates a `SortedVec<T>` from a vector that is already sorted.
+    ///
+    /// # Errors
+    ///
+    /// If `vec` is not sorted, the error holds `vec` and the index of the first element that is
+    /// out of order.
+    pub fn try_from_vec(vec: Vec<T>) -> Result<Self, UnsortedVecError<T>> {
+        match SortedSlice::new(&vec) {
+            Ok(_) => Ok(Self(vec)),
+            Err(e) => Err(UnsortedVecError { index: e.index, vec }),
+        }
+    }
+
+    /// Inserts `value` after any elements that compare `Equal` to it.
+    ///
+    /// Returns the index `value` was inserted at.
+    pub fn insert(&mut self, value: T) -> usize {
+        let index =
+            self.0.partition_point(|elem| T::arbitrary_cmp(elem, &value) != Ordering::Greater);
+        self.0.insert(index, value);
+        index
+    }
+
+    /// Removes and returns the first element that compares `Equal` to `x`, if there is one.
+    pub fn remove<Q: ?Sized>(&mut self, x: &Q) -> Option<T>
+    where
+        T: ArbitraryOrd<Q>,
+    {
+        let index = self.partition_point(x, |ord| ord == Ordering::Less);
+        match self.0.get(index) {
+            Some(elem) if elem.arbitrary_cmp(x) == Ordering::Equal => Some(self.0.remove(index)),
+            _ => None,
+        }
+    }
+
+    /// Merges all elements of `other` into `self`, in linear time.
+    ///
+    /// Elements of `other` are placed after elements of `self` that compare `Equal` to them.
+    pub fn merge(&mut self, other: SortedVec<T>) {
+        let mut merged = Vec::with_capacity(self.0.len() + other.0.len());
+        let mut a = core::mem::take(&mut self.0).into_iter().peekable();
+        let mut b = other.0.into_iter().peekable();
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let next = if T::arbitrary_cmp(y, x) == Ordering::Less { b.next() } else { a.next() };
+            merged.extend(next);
+        }
+        merged.extend(a);
+        merged.extend(b);
+        self.0 = merged;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for SortedVec<T> {
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Deref for SortedVec<T> {
+    type Target = SortedSlice<T>;
+
+    fn deref(&self) -> &Self::Target { self.as_sorted_slice() }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AsRef<[T]> for SortedVec<T> {
+    fn as_ref(&self) -> &[T] { &self.0 }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Borrow<SortedSlice<T>> for SortedVec<T> {
+    fn borrow(&self) -> &SortedSlice<T> { self.as_sorted_slice() }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<SortedVec<T>> for Vec<T> {
+    fn from(vec: SortedVec<T>) -> Self { vec.0 }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> TryFrom<Vec<T>> for SortedVec<T> {
+    type Error = UnsortedVecError<T>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> { Self::try_from_vec(vec) }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> FromIterator<T> for SortedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_unsorted(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ArbitraryOrd> Extend<T> for SortedVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.merge(iter.into_iter().collect());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoIterator for SortedVec<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.into_iter() }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> IntoIterator for &'a SortedVec<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.iter() }
+}
+
+/// Error returned when creating a [`SortedVec`] from a vector that is not sorted.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnsortedVecError<T> {
+    /// The index of the first element that is out of order.
+    pub index: usize,
+    /// The vector that is not sorted, unchanged.
+    pub vec: Vec<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> fmt::Display for UnsortedVecError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "element at index {} is out of order", self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for UnsortedVecError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    fn p(x: u32, y: u32) -> Point { Point { x, y } }
+
+    /// Compares only the `x` of a point, so points with the same `x` are `Equal`.
+    impl ArbitraryOrd for Point {
+        fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.x.cmp(&other.x) }
+    }
+
+    /// A bare `x` coordinate, for heterogeneous lookups.
+    struct X(u32);
+
+    impl PartialEq<X> for Point {
+        fn eq(&self, other: &X) -> bool { self.x == other.0 }
+    }
+
+    impl ArbitraryOrd<X> for Point {
+        fn arbitrary_cmp(&self, other: &X) -> Ordering { self.x.cmp(&other.0) }
+    }
+
+    #[test]
+    fn slice_validation() {
+        let points = [p(0, 0), p(1, 0), p(1, 1), p(3, 0)];
+        let sorted = SortedSlice::new(&points).unwrap();
+        assert_eq!(sorted.as_slice(), points);
+        assert_eq!(sorted.len(), 4);
+
+        let unsorted = [p(0, 0), p(2, 0), p(1, 0)];
+        let err = SortedSlice::new(&unsorted).unwrap_err();
+        assert_eq!((err.index, err.prev, err.next), (2, &p(2, 0), &p(1, 0)));
+
+        let empty: &SortedSlice<Point> = Default::default();
+        assert!(empty.is_empty());
+        assert!(<&SortedSlice<Point>>::try_from(&[p(1, 0), p(0, 0)][..]).is_err());
+    }
+
+    #[test]
+    fn slice_search() {
+        let points = [p(0, 0), p(1, 0), p(1, 1), p(3, 0)];
+        let sorted = SortedSlice::new(&points).unwrap();
+
+        assert!(sorted.contains(&p(3, 9)));
+        assert!(sorted.contains(&X(1)));
+        assert!(!sorted.contains(&X(2)));
+        assert_eq!(sorted.binary_search(&X(2)), Err(3));
+        assert_eq!(sorted.binary_search(&X(0)), Ok(0));
+    }
+
+    #[test]
+    fn slice_range() {
+        let points = [p(0, 0), p(1, 0), p(1, 1), p(3, 0)];
+        let sorted = SortedSlice::new(&points).unwrap();
+
+        assert_eq!(sorted.range(X(1)..X(3)).as_slice(), [p(1, 0), p(1, 1)]);
+        assert_eq!(sorted.range(X(1)..=X(3)).as_slice(), &points[1..]);
+        assert_eq!(sorted.range(..X(1)).as_slice(), [p(0, 0)]);
+        assert_eq!(sorted.range((Bound::Excluded(X(1)), Bound::Unbounded)).as_slice(), [p(3, 0)]);
+        assert_eq!(sorted.range::<X, _>(..).as_slice(), points);
+        assert!(sorted.range(X(2)..X(3)).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn slice_range_panics() {
+        let _ = SortedSlice::new(&[p(0, 0), p(1, 0), p(2, 0)]).unwrap().range(X(2)..X(1));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vec_constructors() {
+        use alloc::vec;
+
+        let sorted = SortedVec::from_unsorted(vec![p(3, 0), p(1, 0), p(0, 0), p(1, 1)]);
+        assert_eq!(sorted.as_slice(), [p(0, 0), p(1, 0), p(1, 1), p(3, 0)]);
+
+        let sorted = SortedVec::try_from_vec(sorted.into_vec()).unwrap();
+        assert_eq!(sorted, [p(3, 0), p(0, 0), p(1, 0), p(1, 1)].into_iter().collect());
+
+        let err = SortedVec::try_from(vec![p(0, 0), p(2, 0), p(1, 0)]).unwrap_err();
+        assert_eq!(err.index, 2);
+        assert_eq!(err.vec, [p(0, 0), p(2, 0), p(1, 0)]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vec_insert_remove() {
+        let mut sorted = SortedVec::new();
+
+        assert_eq!(sorted.insert(p(2, 0)), 0);
+        assert_eq!(sorted.insert(p(0, 0)), 0);
+        assert_eq!(sorted.insert(p(2, 1)), 2);
+        assert_eq!(sorted.insert(p(1, 0)), 1);
+        assert_eq!(sorted.as_slice(), [p(0, 0), p(1, 0), p(2, 0), p(2, 1)]);
+
+        assert_eq!(sorted.remove(&X(1)), Some(p(1, 0)));
+        assert_eq!(sorted.remove(&X(1)), None);
+        assert_eq!(sorted.remove(&X(2)), Some(p(2, 0)));
+        assert_eq!(sorted.len(), 2);
+        assert_eq!(sorted.pop(), Some(p(2, 1)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vec_merge() {
+        let mut a: SortedVec<_> = [p(0, 0), p(2, 0), p(4, 0)].into_iter().collect();
+        let b: SortedVec<_> = [p(1, 1), p(2, 1), p(5, 1)].into_iter().collect();
+
+        a.merge(b);
+        assert_eq!(a.as_slice(), [p(0, 0), p(1, 1), p(2, 0), p(2, 1), p(4, 0), p(5, 1)]);
+
+        a.extend([p(3, 2), p(0, 2)]);
+        assert_eq!(a.range(X(0)..X(2)).as_slice(), [p(0, 0), p(0, 2), p(1, 1)]);
+        assert_eq!(a.len(), 8);
+        assert!(crate::ArbitrarySliceExt::is_arbitrarily_sorted(a.as_slice()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_owned() {
+        let points = [p(0, 0), p(1, 0)];
+        let owned = SortedSlice::new(&points).unwrap().to_owned();
+        assert_eq!(owned.as_slice(), points);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn error_message() {
+        use alloc::string::ToString;
+        use alloc::vec;
+
+        let err = SortedVec::try_from_vec(vec![1, 0]).unwrap_err();
+        assert_eq!(err.to_string(), "element at index 1 is out of order");
+    }
+}
@@ -0,0 +1,644 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A map and set sorted by a comparator value, see [`SortedMapBy`] and [`SortedSetBy`].
+//!
+//! [`ArbitraryOrd`] fixes a single ordering per type. These collections instead store a
+//! [`Comparator`] and use it for every operation, so the ordering can be chosen at runtime, for
+//! example a user selected sort column. The comparator can be any `Comparator`, including a
+//! closure wrapped with [`comparator::from_fn`] or a `Box<dyn Comparator<K>>`, and defaults to
+//! [`ArbitraryOrder`], the ordering defined by `ArbitraryOrd`.
+//!
+//! Lookups take a `&K` since the comparator only knows how to compare keys.
+//!
+//! # Examples
+//!
+//! ```
+//! use ordered::comparator::{self, Comparator};
+//! use ordered::SortedSetBy;
+//!
+//! let descending = true;
+//! let cmp: Box<dyn Comparator<u32>> = if descending {
+//!     Box::new(comparator::natural().reverse())
+//! } else {
+//!     Box::new(comparator::natural())
+//! };
+//!
+//! let set = SortedSetBy::from_iter_with_comparator([2, 7, 1], cmp);
+//!
+//! assert_eq!(set.as_slice(), [7, 2, 1]);
+//! assert!(set.contains(&2));
+//! ```
+
+use alloc::vec::{self, Vec};
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, RangeBounds};
+use core::{fmt, slice};
+
+use crate::comparable::check_range_by;
+use crate::comparator::{self, ArbitraryOrder, Comparator};
+pub use crate::entries::{
+    Entry, Iter, IterMut, Keys, OccupiedEntry, VacantEntry, Values, ValuesMut,
+};
+use crate::{entries, ArbitraryOrd};
+
+/// A map that stores its entries in a vector sorted by a [`Comparator`] of type `C`.
+///
+/// The map is created with its comparator, by [`with_comparator`] or [`from_vec_with_comparator`]
+/// for example, and otherwise has the methods of `OrderedVecMap`. Unlike there, lookups take a
+/// `&K`.
+///
+/// The comparator must not change its ordering while it is in the map. The map will not panic if
+/// it does, but lookups may miss entries.
+///
+/// [`with_comparator`]: SortedMapBy::with_comparator
+/// [`from_vec_with_comparator`]: SortedMapBy::from_vec_with_comparator
+///
+/// # Examples
+///
+/// ```
+/// use ordered::comparator;
+/// use ordered::SortedMapBy;
+///
+/// // Sort by length first, as selected at runtime.
+/// let by_len = comparator::from_fn(|a: &&str, b: &&str| (a.len(), a).cmp(&(b.len(), b)));
+///
+/// let mut map = SortedMapBy::with_comparator(by_len);
+/// map.insert("ccc", 3);
+/// map.insert("a", 1);
+/// *map.entry("bb").or_insert(0) += 2;
+///
+/// assert_eq!(map.keys().collect::<Vec<_>>(), [&"a", &"bb", &"ccc"]);
+/// assert_eq!(map[&"bb"], 2);
+/// ```
+#[derive(Clone)]
+pub struct SortedMapBy<K, V, C = ArbitraryOrder<K>> {
+    entries: Vec<(K, V)>,
+    cmp: C,
+}
+
+impl<K: ArbitraryOrd, V> SortedMapBy<K, V> {
+    /// Creates a new empty map sorted by [`ArbitraryOrd`].
+    #[must_use]
+    pub const fn new() -> Self { Self::with_comparator(comparator::arbitrary()) }
+
+    /// Creates a new empty map sorted by [`ArbitraryOrd`], with space for at least `capacity`
+    /// entries.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_comparator(capacity, comparator::arbitrary())
+    }
+}
+
+impl<K, V, C> SortedMapBy<K, V, C> {
+    /// Creates a new empty map sorted by `cmp`.
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self { Self { entries: Vec::new(), cmp } }
+
+    /// Creates a new empty map sorted by `cmp`, with space for at least `capacity` entries.
+    #[must_use]
+    pub fn with_capacity_and_comparator(capacity: usize, cmp: C) -> Self {
+        Self { entries: Vec::with_capacity(capacity), cmp }
+    }
+
+    /// Returns the comparator the map is sorted by.
+    #[must_use]
+    pub fn comparator(&self) -> &C { &self.cmp }
+
+    /// Returns the number of entries in the map.
+    #[must_use]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Returns `true` if the map contains no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Removes all entries, keeping the comparator.
+    pub fn clear(&mut self) { self.entries.clear() }
+
+    /// Returns the entries as a slice, sorted by key.
+    #[must_use]
+    pub fn as_slice(&self) -> &[(K, V)] { &self.entries }
+
+    /// Returns the entries as a vector, sorted by key, dropping the comparator.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<(K, V)> { self.entries }
+
+    /// Returns the first entry, the one the comparator orders first.
+    #[must_use]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> { self.entries.first().map(|(k, v)| (k, v)) }
+
+    /// Returns the last entry, the one the comparator orders last.
+    #[must_use]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> { self.entries.last().map(|(k, v)| (k, v)) }
+
+    /// Removes and returns the first entry.
+    ///
+    /// The rest of the vector is moved down to fill the gap, which [`pop_last`] avoids.
+    ///
+    /// [`pop_last`]: Self::pop_last
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some(self.entries.remove(0))
+        }
+    }
+
+    /// Removes and returns the last entry.
+    pub fn pop_last(&mut self) -> Option<(K, V)> { self.entries.pop() }
+
+    /// Retains only the entries for which `f` returns `true`.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        self.entries.retain_mut(|(k, v)| f(k, v));
+    }
+
+    /// Returns an iterator over the entries, in the order of the comparator.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, K, V> { Iter::new(&self.entries) }
+
+    /// Returns an iterator over the entries with mutable values, in the order of the comparator.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> { IterMut::new(&mut self.entries) }
+
+    /// Returns an iterator over the keys, in the order of the comparator.
+    #[must_use]
+    pub fn keys(&self) -> Keys<'_, K, V> { Keys::new(&self.entries) }
+
+    /// Returns an iterator over the values, in the order of their keys.
+    #[must_use]
+    pub fn values(&self) -> Values<'_, K, V> { Values::new(&self.entries) }
+
+    /// Returns an iterator over mutable values, in the order of their keys.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> { ValuesMut::new(&mut self.entries) }
+}
+
+impl<K, V, C: Comparator<K>> SortedMapBy<K, V, C> {
+    /// Creates a map sorted by `cmp` from a vector of entries, sorting it once.
+    ///
+    /// If `cmp` orders keys as equal the first key is kept with the last value, as if the entries
+    /// were inserted in order.
+    #[must_use]
+    pub fn from_vec_with_comparator(mut entries: Vec<(K, V)>, cmp: C) -> Self {
+        entries::sort_dedup(&mut entries, |a, b| cmp.compare(a, b));
+        Self { entries, cmp }
+    }
+
+    /// Creates a map sorted by `cmp` from an iterator of entries, see
+    /// [`from_vec_with_comparator`](Self::from_vec_with_comparator).
+    #[must_use]
+    pub fn from_iter_with_comparator<I: IntoIterator<Item = (K, V)>>(iter: I, cmp: C) -> Self {
+        Self::from_vec_with_comparator(iter.into_iter().collect(), cmp)
+    }
+
+    /// Binary searches for `key`, returning its index or the index it would be inserted at.
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(k, _)| self.cmp.compare(k, key))
+    }
+
+    /// Returns a reference to the value corresponding to `key`.
+    pub fn get(&self, key: &K) -> Option<&V> { self.get_key_value(key).map(|(_, v)| v) }
+
+    /// Returns the key-value pair corresponding to `key`.
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        let (k, v) = &self.entries[self.search(key).ok()?];
+        Some((k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to `key`.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Returns `true` if the map contains a value for `key`.
+    pub fn contains_key(&self, key: &K) -> bool { self.search(key).is_ok() }
+
+    /// Inserts a key-value pair, returning the old value if the comparator finds an equal key.
+    ///
+    /// The stored key is kept in that case, even if it differs from `key` in ways the comparator
+    /// ignores.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Removes `key` from the map, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> { self.remove_entry(key).map(|(_, v)| v) }
+
+    /// Removes `key` from the map, returning the stored key and value if it was present.
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let index = self.search(key).ok()?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Returns an iterator over the entries with keys within `range`, in the order of the
+    /// comparator.
+    ///
+    /// # Panics
+    ///
+    /// If the comparator orders the start of the range after the end, or if they are equal and
+    /// both excluded.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Iter<'_, K, V> {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        check_range_by(start, end, |a, b| self.cmp.compare(a, b));
+        Iter::new(entries::range_by(&self.entries, start, end, |q, (k, _)| self.cmp.compare(q, k)))
+    }
+
+    /// Returns the entry for `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let search = self.search(&key);
+        entries::entry(&mut self.entries, search, key)
+    }
+}
+
+impl<K, V, C: Default> Default for SortedMapBy<K, V, C> {
+    fn default() -> Self { Self::with_comparator(C::default()) }
+}
+
+impl<K: PartialEq, V: PartialEq, C> PartialEq for SortedMapBy<K, V, C> {
+    /// Compares the entries, the comparators are not compared.
+    fn eq(&self, other: &Self) -> bool { self.entries == other.entries }
+}
+
+impl<K: Eq, V: Eq, C> Eq for SortedMapBy<K, V, C> {}
+
+impl<K: Hash, V: Hash, C> Hash for SortedMapBy<K, V, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.entries.hash(state) }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, C> fmt::Debug for SortedMapBy<K, V, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, C: Comparator<K>> Index<&K> for SortedMapBy<K, V, C> {
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to `key`.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is not present in the map.
+    fn index(&self, key: &K) -> &V { self.get(key).expect("no entry found for key") }
+}
+
+impl<K, V, C: Comparator<K> + Default> FromIterator<(K, V)> for SortedMapBy<K, V, C> {
+    /// Collects the entries and sorts them with the default comparator, use
+    /// [`SortedMapBy::from_iter_with_comparator`] for any other comparator.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from_iter_with_comparator(iter, C::default())
+    }
+}
+
+impl<K, V, C: Comparator<K>> Extend<(K, V)> for SortedMapBy<K, V, C> {
+    /// Appends the entries and sorts the map once, with the same result as inserting them in
+    /// order.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+        entries::sort_dedup(&mut self.entries, |a, b| self.cmp.compare(a, b));
+    }
+}
+
+impl<K, V, C> IntoIterator for SortedMapBy<K, V, C> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter { self.entries.into_iter() }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a SortedMapBy<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a mut SortedMapBy<K, V, C> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// A set that stores its elements in a vector sorted by a [`Comparator`] of type `C`.
+///
+/// See [`SortedMapBy`] for the requirements on the comparator.
+#[derive(Clone)]
+pub struct SortedSetBy<T, C = ArbitraryOrder<T>> {
+    elements: Vec<T>,
+    cmp: C,
+}
+
+impl<T: ArbitraryOrd> SortedSetBy<T> {
+    /// Creates a new empty set sorted by [`ArbitraryOrd`].
+    #[must_use]
+    pub const fn new() -> Self { Self::with_comparator(comparator::arbitrary()) }
+
+    /// Creates a new empty set sorted by [`ArbitraryOrd`], with space for at least `capacity`
+    /// elements.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_comparator(capacity, comparator::arbitrary())
+    }
+}
+
+impl<T, C> SortedSetBy<T, C> {
+    /// Creates a new empty set sorted by `cmp`.
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self { Self { elements: Vec::new(), cmp } }
+
+    /// Creates a new empty set sorted by `cmp`, with space for at least `capacity` elements.
+    #[must_use]
+    pub fn with_capacity_and_comparator(capacity: usize, cmp: C) -> Self {
+        Self { elements: Vec::with_capacity(capacity), cmp }
+    }
+
+    /// Returns the comparator the set is sorted by.
+    #[must_use]
+    pub fn comparator(&self) -> &C { &self.cmp }
+
+    /// Returns the number of elements in the set.
+    #[must_use]
+    pub fn len(&self) -> usize { self.elements.len() }
+
+    /// Returns `true` if the set contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.elements.is_empty() }
+
+    /// Removes all elements, keeping the comparator.
+    pub fn clear(&mut self) { self.elements.clear() }
+
+    /// Returns the elements as a slice, in the order of the comparator.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] { &self.elements }
+
+    /// Returns the elements as a vector, in the order of the comparator, dropping the comparator.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> { self.elements }
+
+    /// Returns the element the comparator orders first.
+    #[must_use]
+    pub fn first(&self) -> Option<&T> { self.elements.first() }
+
+    /// Returns the element the comparator orders last.
+    #[must_use]
+    pub fn last(&self) -> Option<&T> { self.elements.last() }
+
+    /// Removes and returns the first element, moving the rest of the vector down.
+    pub fn pop_first(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            None
+        } else {
+            Some(self.elements.remove(0))
+        }
+    }
+
+    /// Removes and returns the last element.
+    pub fn pop_last(&mut self) -> Option<T> { self.elements.pop() }
+
+    /// Retains only the elements for which `f` returns `true`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) { self.elements.retain(f) }
+
+    /// Returns an iterator over the elements, in the order of the comparator.
+    pub fn iter(&self) -> slice::Iter<'_, T> { self.elements.iter() }
+}
+
+impl<T, C: Comparator<T>> SortedSetBy<T, C> {
+    /// Creates a set sorted by `cmp` from a vector of elements, sorting it once.
+    ///
+    /// Of the elements that `cmp` orders as equal only the first is kept, as if they were inserted
+    /// in order.
+    #[must_use]
+    pub fn from_vec_with_comparator(mut elements: Vec<T>, cmp: C) -> Self {
+        entries::sort_dedup_elements(&mut elements, |a, b| cmp.compare(a, b));
+        Self { elements, cmp }
+    }
+
+    /// Creates a set sorted by `cmp` from an iterator of elements, see
+    /// [`from_vec_with_comparator`](Self::from_vec_with_comparator).
+    #[must_use]
+    pub fn from_iter_with_comparator<I: IntoIterator<Item = T>>(iter: I, cmp: C) -> Self {
+        Self::from_vec_with_comparator(iter.into_iter().collect(), cmp)
+    }
+
+    /// Binary searches for `value`, returning its index or the index it would be inserted at.
+    fn search(&self, value: &T) -> Result<usize, usize> {
+        self.elements.binary_search_by(|t| self.cmp.compare(t, value))
+    }
+
+    /// Returns `true` if the set contains `value`.
+    pub fn contains(&self, value: &T) -> bool { self.search(value).is_ok() }
+
+    /// Returns a reference to the stored element that the comparator finds equal to `value`.
+    pub fn get(&self, value: &T) -> Option<&T> {
+        let index = self.search(value).ok()?;
+        Some(&self.elements[index])
+    }
+
+    /// Adds `value` to the set, returning `false` if the comparator finds an equal element.
+    ///
+    /// The stored element is not replaced in that case.
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.search(&value) {
+            Ok(_) => false,
+            Err(index) => {
+                self.elements.insert(index, value);
+                true
+            }
+        }
+    }
+
+    /// Removes `value` from the set, returning `true` if it was present.
+    pub fn remove(&mut self, value: &T) -> bool { self.take(value).is_some() }
+
+    /// Removes and returns the stored element equal to `value`.
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        let index = self.search(value).ok()?;
+        Some(self.elements.remove(index))
+    }
+
+    /// Returns an iterator over the elements within `range`, in the order of the comparator.
+    ///
+    /// # Panics
+    ///
+    /// If the comparator orders the start of the range after the end, or if they are equal and
+    /// both excluded.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> slice::Iter<'_, T> {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        check_range_by(start, end, |a, b| self.cmp.compare(a, b));
+        entries::range_by(&self.elements, start, end, |q, t| self.cmp.compare(q, t)).iter()
+    }
+}
+
+impl<T, C: Default> Default for SortedSetBy<T, C> {
+    fn default() -> Self { Self::with_comparator(C::default()) }
+}
+
+impl<T: PartialEq, C> PartialEq for SortedSetBy<T, C> {
+    /// Compares the elements, the comparators are not compared.
+    fn eq(&self, other: &Self) -> bool { self.elements == other.elements }
+}
+
+impl<T: Eq, C> Eq for SortedSetBy<T, C> {}
+
+impl<T: Hash, C> Hash for SortedSetBy<T, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.elements.hash(state) }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for SortedSetBy<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for SortedSetBy<T, C> {
+    /// Collects the elements and sorts them with the default comparator, use
+    /// [`SortedSetBy::from_iter_with_comparator`] for any other comparator.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_iter_with_comparator(iter, C::default())
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for SortedSetBy<T, C> {
+    /// Appends the elements and sorts the set once, with the same result as inserting them in
+    /// order.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.elements.extend(iter);
+        entries::sort_dedup_elements(&mut self.elements, |a, b| self.cmp.compare(a, b));
+    }
+}
+
+impl<T, C> IntoIterator for SortedSetBy<T, C> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.elements.into_iter() }
+}
+
+impl<'a, T, C> IntoIterator for &'a SortedSetBy<T, C> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use core::ops::Bound;
+
+    use super::*;
+    use crate::entries::tests::{p, Point};
+
+    /// Orders points by the column selected at runtime, then by `ArbitraryOrd`.
+    fn by_column(column: usize) -> impl Comparator<Point> {
+        comparator::from_fn(move |a: &Point, b: &Point| {
+            let key = |p: &Point| if column == 0 { p.x } else { p.y };
+            key(a).cmp(&key(b))
+        })
+        .then(comparator::arbitrary())
+    }
+
+    #[test]
+    fn default_comparator() {
+        let mut map = SortedMapBy::new();
+        map.insert(p(1, 0), "b");
+        map.insert(p(0, 9), "a");
+        assert_eq!(map.insert(p(1, 0), "c"), Some("b"));
+
+        assert_eq!(map.as_slice(), [(p(0, 9), "a"), (p(1, 0), "c")]);
+        assert_eq!(map[&p(0, 9)], "a");
+
+        let set: SortedSetBy<_> = [p(2, 0), p(0, 3), p(2, 0)].into_iter().collect();
+        assert_eq!(set.as_slice(), [p(0, 3), p(2, 0)]);
+    }
+
+    #[test]
+    fn runtime_comparator() {
+        let points = [p(0, 2), p(1, 1), p(2, 0), p(3, 1)];
+        let by_x =
+            SortedMapBy::from_iter_with_comparator(points.into_iter().zip(0..), by_column(0));
+        let mut by_y =
+            SortedMapBy::from_iter_with_comparator(points.into_iter().zip(0..), by_column(1));
+
+        assert_eq!(by_x.values().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(by_y.values().copied().collect::<Vec<_>>(), [2, 1, 3, 0]);
+        assert_eq!(by_y.range(p(0, 1)..p(9, 1)).count(), 2);
+        assert_eq!(by_y.range((Bound::Excluded(p(9, 1)), Bound::Unbounded)).count(), 1);
+
+        assert_eq!(by_y.get(&p(1, 1)), Some(&1));
+        assert_eq!(by_y.remove(&p(2, 0)), Some(2));
+        assert!(!by_y.contains_key(&p(2, 0)));
+        assert_eq!(by_y.pop_first(), Some((p(1, 1), 1)));
+        assert_eq!(by_y.pop_last(), Some((p(0, 2), 0)));
+    }
+
+    #[test]
+    fn comparator_equal_keys() {
+        // Only `x` is compared, so `y` shows which key and value are kept.
+        let by_x = || comparator::from_fn(|a: &Point, b: &Point| a.x.cmp(&b.x));
+
+        let mut map = SortedMapBy::from_vec_with_comparator(
+            alloc::vec![(p(1, 0), 'a'), (p(0, 0), 'b'), (p(1, 1), 'c')],
+            by_x(),
+        );
+        assert_eq!(map.as_slice(), [(p(0, 0), 'b'), (p(1, 0), 'c')]);
+        map.extend([(p(0, 1), 'd'), (p(2, 0), 'e')]);
+        assert_eq!(map.as_slice(), [(p(0, 0), 'd'), (p(1, 0), 'c'), (p(2, 0), 'e')]);
+
+        let mut set = SortedSetBy::from_iter_with_comparator([p(1, 0), p(1, 1)], by_x());
+        set.extend([p(0, 0), p(1, 2)]);
+        assert_eq!(set.as_slice(), [p(0, 0), p(1, 0)]);
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = SortedMapBy::with_comparator(by_column(1));
+        *map.entry(p(0, 2)).or_insert(0) += 1;
+        *map.entry(p(0, 2)).or_insert(0) += 1;
+        map.entry(p(5, 0)).or_default();
+
+        match map.entry(p(5, 0)) {
+            Entry::Occupied(entry) => assert_eq!(entry.remove_entry(), (p(5, 0), 0)),
+            Entry::Vacant(_) => panic!("entry should be occupied"),
+        }
+        assert_eq!(map.into_vec(), [(p(0, 2), 2)]);
+    }
+
+    #[test]
+    fn boxed_comparator() {
+        let cmps: [Box<dyn Comparator<u32>>; 2] =
+            [Box::new(comparator::natural()), Box::new(comparator::natural().reverse())];
+        let [ascending, descending] = cmps;
+
+        let asc = SortedSetBy::from_iter_with_comparator([3, 1, 2, 1], ascending);
+        let mut desc = SortedSetBy::with_comparator(descending);
+        desc.extend([3, 1, 2, 1]);
+
+        assert_eq!(asc.as_slice(), [1, 2, 3]);
+        assert_eq!(desc.as_slice(), [3, 2, 1]);
+        assert!(desc.range((Bound::Included(3), Bound::Included(2))).eq(&[3, 2]));
+        assert!(!desc.insert(2));
+        assert_eq!(desc.take(&2), Some(2));
+        assert_eq!(desc.into_vec(), [3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn reversed_range() {
+        let set = SortedSetBy::with_comparator(comparator::natural::<u32>().reverse());
+        let _ = set.range(2..3);
+    }
+
+    #[test]
+    #[should_panic(expected = "no entry found for key")]
+    fn index_panics() {
+        let map = SortedMapBy::<Point, u32>::new();
+        let _ = map[&p(0, 0)];
+    }
+}
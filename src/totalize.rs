@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Total orders for types that only implement `PartialOrd`.
+//!
+//! [`Totalize`] wraps a `PartialOrd` type together with an [`IncomparablePolicy`] that decides what
+//! happens when two values are incomparable, and implements `Ord` and `Eq` consistently with that
+//! policy. This allows such types to be used as `BTreeMap` keys when the incomparable values are
+//! known to never reach the map.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use ordered::Totalize;
+//!
+//! let mut map = BTreeMap::new();
+//! map.insert(Totalize::<f64>::try_new(2.5).expect("not NaN"), "two and a half");
+//! map.insert(Totalize::try_new(-1.0).expect("not NaN"), "minus one");
+//!
+//! assert_eq!(map.values().copied().collect::<Vec<_>>(), ["minus one", "two and a half"]);
+//! assert!(Totalize::<f64>::try_new(f64::NAN).is_err());
+//! ```
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use crate::ArbitraryOrd;
+
+/// A policy that decides how [`Totalize`] orders two values that are incomparable.
+pub trait IncomparablePolicy<T: ?Sized> {
+    /// Returns the order of `a` and `b`, called when `a.partial_cmp(b)` returns `None`.
+    #[track_caller]
+    fn incomparable(a: &T, b: &T) -> Ordering;
+}
+
+/// Panics if two values are incomparable.
+///
+/// This is the default policy, it is the right choice if incomparable values are never expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Panic {}
+
+impl<T: ?Sized> IncomparablePolicy<T> for Panic {
+    #[track_caller]
+    fn incomparable(_: &T, _: &T) -> Ordering {
+        panic!("attempted to order incomparable values in `Totalize`")
+    }
+}
+
+/// Treats incomparable values as equal.
+///
+/// This is only a total order if incomparable values are never mixed with comparable ones, for
+/// example if all values are incomparable to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsEqual {}
+
+impl<T: ?Sized> IncomparablePolicy<T> for AsEqual {
+    fn incomparable(_: &T, _: &T) -> Ordering { Ordering::Equal }
+}
+
+/// Orders incomparable values using the [`ArbitraryOrd`] impl of `T`.
+///
+/// This is only a total order if the `ArbitraryOrd` impl agrees with `PartialOrd` on the values
+/// that are comparable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fallback {}
+
+impl<T: ArbitraryOrd + ?Sized> IncomparablePolicy<T> for Fallback {
+    fn incomparable(a: &T, b: &T) -> Ordering { a.arbitrary_cmp(b) }
+}
+
+/// A wrapper type that implements `Ord` from the `PartialOrd` impl of `T`.
+///
+/// Incomparable values are ordered according to the policy `P`. `PartialEq` and `Eq` are
+/// implemented using the same order so that they agree with `Ord`.
+///
+/// # Examples
+///
+/// ```
+/// use ordered::totalize::{AsEqual, Totalize};
+///
+/// let nan = Totalize::<f64, AsEqual>::new(f64::NAN);
+///
+/// assert!(Totalize::<f64>::new(1.0) < Totalize::new(2.0));
+/// assert!(nan == Totalize::new(f64::NAN));
+/// ```
+#[repr(transparent)]
+pub struct Totalize<T, P = Panic>(pub T, PhantomData<fn() -> P>);
+
+impl<T, P> Totalize<T, P> {
+    /// Creates a new totally ordered type without checking `value`.
+    pub const fn new(value: T) -> Self { Self(value, PhantomData) }
+
+    /// Returns the inner object.
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+impl<T: PartialOrd, P> Totalize<T, P> {
+    /// Creates a new totally ordered type.
+    ///
+    /// # Errors
+    ///
+    /// If `value` is not comparable to itself, for example a `NaN` float.
+    pub fn try_new(value: T) -> Result<Self, IncomparableError> {
+        check(&value)?;
+        Ok(Self::new(value))
+    }
+
+    /// Creates a `Totalize<T, P>` from a reference.
+    ///
+    /// # Errors
+    ///
+    /// If `value` is not comparable to itself, for example a `NaN` float.
+    #[allow(clippy::ptr_as_ptr)]
+    pub fn try_from_ref(value: &T) -> Result<&Self, IncomparableError> {
+        check(value)?;
+        // SAFETY: `Totalize` is `repr(transparent)` over `T` and `PhantomData` is zero-sized.
+        Ok(unsafe { &*(value as *const T as *const Self) })
+    }
+}
+
+/// Checks that `value` is comparable to itself.
+fn check<T: PartialOrd>(value: &T) -> Result<(), IncomparableError> {
+    match value.partial_cmp(value) {
+        Some(Ordering::Equal) => Ok(()),
+        _ => Err(IncomparableError),
+    }
+}
+
+impl<T: PartialOrd, P: IncomparablePolicy<T>> PartialOrd for Totalize<T, P> {
+    #[track_caller]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl<T: PartialOrd, P: IncomparablePolicy<T>> Ord for Totalize<T, P> {
+    #[track_caller]
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.0.partial_cmp(&other.0) {
+            Some(ord) => ord,
+            None => P::incomparable(&self.0, &other.0),
+        }
+    }
+}
+
+impl<T: PartialOrd, P: IncomparablePolicy<T>> PartialEq for Totalize<T, P> {
+    #[track_caller]
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl<T: PartialOrd, P: IncomparablePolicy<T>> Eq for Totalize<T, P> {}
+
+impl<T: Clone, P> Clone for Totalize<T, P> {
+    fn clone(&self) -> Self { Self::new(self.0.clone()) }
+}
+
+impl<T: Copy, P> Copy for Totalize<T, P> {}
+
+impl<T: fmt::Debug, P> fmt::Debug for Totalize<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Totalize").field(&self.0).finish()
+    }
+}
+
+impl<T: fmt::Display, P> fmt::Display for Totalize<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.0, f) }
+}
+
+impl<T, P> AsRef<T> for Totalize<T, P> {
+    fn as_ref(&self) -> &T { &self.0 }
+}
+
+impl<T, P> Borrow<T> for Totalize<T, P> {
+    fn borrow(&self) -> &T { &self.0 }
+}
+
+impl<T, P> Deref for Totalize<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+/// Error returned when creating a [`Totalize`] from a value that is not comparable to itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IncomparableError;
+
+impl fmt::Display for IncomparableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("value is not comparable to itself")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IncomparableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparable_values() {
+        let a = Totalize::<f64>::new(1.0);
+        let b = Totalize::new(2.0);
+
+        assert!(a < b);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+        assert_eq!(Totalize::<f64>::new(-0.0), Totalize::new(0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "incomparable values")]
+    fn panic_policy() { let _ = Totalize::<f64>::new(f64::NAN).cmp(&Totalize::new(1.0)); }
+
+    #[test]
+    fn as_equal_policy() {
+        let nan = Totalize::<f64, AsEqual>::new(f64::NAN);
+
+        assert_eq!(nan.cmp(&Totalize::new(1.0)), Ordering::Equal);
+        assert_eq!(nan, Totalize::new(f64::NAN));
+    }
+
+    #[test]
+    fn fallback_policy() {
+        /// Only comparable if `0` is the same, falls back to comparing both fields.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Pair(u32, u32);
+
+        impl PartialOrd for Pair {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                if self.0 == other.0 {
+                    Some(self.1.cmp(&other.1))
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl ArbitraryOrd for Pair {
+            fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+                (self.0, self.1).cmp(&(other.0, other.1))
+            }
+        }
+
+        let a = Totalize::<_, Fallback>::new(Pair(1, 5));
+
+        assert!(a < Totalize::new(Pair(1, 6)));
+        assert!(a < Totalize::new(Pair(2, 0)));
+        assert!(a > Totalize::new(Pair(0, 9)));
+    }
+
+    #[test]
+    fn checked_constructors() {
+        assert!(Totalize::<f64>::try_new(1.0).is_ok());
+        assert!(Totalize::<f64>::try_from_ref(&1.0).is_ok());
+
+        let err = Totalize::<f32>::try_new(f32::NAN).unwrap_err();
+        assert_eq!(err, IncomparableError);
+        assert!(Totalize::<f32>::try_from_ref(&f32::NAN).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn error_message() {
+        use alloc::string::ToString;
+
+        let err = Totalize::<f64>::try_new(f64::NAN).unwrap_err();
+        assert_eq!(err.to_string(), "value is not comparable to itself");
+    }
+}
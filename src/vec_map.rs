@@ -0,0 +1,409 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A map backed by a sorted vector, see [`OrderedVecMap`].
+
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::ops::{Index, RangeBounds};
+
+pub use crate::entries::{
+    Entry, Iter, IterMut, Keys, OccupiedEntry, VacantEntry, Values, ValuesMut,
+};
+use crate::{entries, ArbitraryOrd, Comparable};
+
+/// A map that stores its entries in a vector sorted by [`ArbitraryOrd`].
+///
+/// Lookups are binary searches and insertions and removals shift the entries after them, so this
+/// is faster and smaller than a `BTreeMap<Ordered<K>, V>` for small maps that are read more than
+/// they are written. The API is the same as `BTreeMap` except keys are not wrapped in
+/// [`Ordered`](crate::Ordered), and lookups take any `Q` that is [`Comparable`] to `K`.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+/// use ordered::{ArbitraryOrd, OrderedVecMap};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// impl ArbitraryOrd for Point {
+///     fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+///         (self.x, self.y).cmp(&(other.x, other.y))
+///     }
+/// }
+///
+/// let a = Point { x: 2, y: 3 };
+/// let b = Point { x: 1, y: 5 };
+///
+/// let mut map: OrderedVecMap<_, _> = [(a, "a"), (b, "b")].into_iter().collect();
+/// *map.entry(a).or_insert("c") = "d";
+///
+/// assert_eq!(map[&a], "d");
+/// assert_eq!(map.keys().collect::<Vec<_>>(), [&b, &a]);
+/// assert_eq!(map.remove(&b), Some("b"));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OrderedVecMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> OrderedVecMap<K, V> {
+    /// Creates a new empty map.
+    #[must_use]
+    pub const fn new() -> Self { Self { entries: Vec::new() } }
+
+    /// Creates a new empty map with space for at least `capacity` entries.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self { Self { entries: Vec::with_capacity(capacity) } }
+
+    /// Returns the number of entries in the map.
+    #[must_use]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Returns `true` if the map contains no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Removes all entries, keeping the allocated capacity.
+    pub fn clear(&mut self) { self.entries.clear() }
+
+    /// Returns the entries as a slice, sorted by key.
+    #[must_use]
+    pub fn as_slice(&self) -> &[(K, V)] { &self.entries }
+
+    /// Returns the entries as a vector, sorted by key.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<(K, V)> { self.entries }
+
+    /// Returns the first entry, the one with the least key.
+    #[must_use]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> { self.entries.first().map(|(k, v)| (k, v)) }
+
+    /// Returns the last entry, the one with the greatest key.
+    #[must_use]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> { self.entries.last().map(|(k, v)| (k, v)) }
+
+    /// Removes and returns the first entry.
+    ///
+    /// Every other entry moves down one place in the vector, so prefer [`pop_last`] when draining
+    /// a large map.
+    ///
+    /// [`pop_last`]: Self::pop_last
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some(self.entries.remove(0))
+        }
+    }
+
+    /// Removes and returns the last entry.
+    pub fn pop_last(&mut self) -> Option<(K, V)> { self.entries.pop() }
+
+    /// Retains only the entries for which `f` returns `true`.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        self.entries.retain_mut(|(k, v)| f(k, v));
+    }
+
+    /// Returns an iterator over the entries, sorted by key.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, K, V> { Iter::new(&self.entries) }
+
+    /// Returns an iterator over the entries with mutable values, sorted by key.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> { IterMut::new(&mut self.entries) }
+
+    /// Returns an iterator over the keys, in order.
+    #[must_use]
+    pub fn keys(&self) -> Keys<'_, K, V> { Keys::new(&self.entries) }
+
+    /// Returns an iterator over the values, sorted by key.
+    #[must_use]
+    pub fn values(&self) -> Values<'_, K, V> { Values::new(&self.entries) }
+
+    /// Returns an iterator over mutable values, sorted by key.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> { ValuesMut::new(&mut self.entries) }
+}
+
+impl<K: ArbitraryOrd, V> OrderedVecMap<K, V> {
+    /// Creates a map from a vector of entries, sorting it once.
+    ///
+    /// If a key appears more than once the first key is kept with the last value, as if the
+    /// entries were inserted in order.
+    #[must_use]
+    pub fn from_vec(mut entries: Vec<(K, V)>) -> Self {
+        entries::sort_dedup(&mut entries, K::arbitrary_cmp);
+        Self { entries }
+    }
+
+    /// Binary searches for `key`, returning its index or the index it would be inserted at.
+    fn search<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Result<usize, usize> {
+        entries::search(&self.entries, entries::key, key)
+    }
+
+    /// Returns a reference to the value corresponding to `key`.
+    pub fn get<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Option<&V> {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair corresponding to `key`.
+    pub fn get_key_value<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> Option<(&K, &V)> {
+        let (k, v) = &self.entries[self.search(key).ok()?];
+        Some((k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to `key`.
+    pub fn get_mut<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Returns `true` if the map contains a value for `key`.
+    pub fn contains_key<Q: Comparable<K> + ?Sized>(&self, key: &Q) -> bool {
+        self.search(key).is_ok()
+    }
+
+    /// Inserts a key-value pair, returning the old value if `key` was already present.
+    ///
+    /// If the key was present the stored key is kept and only the value is replaced, as with
+    /// `BTreeMap::insert`.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Removes `key` from the map, returning its value if it was present.
+    pub fn remove<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<V> {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes `key` from the map, returning the stored key and value if it was present.
+    pub fn remove_entry<Q: Comparable<K> + ?Sized>(&mut self, key: &Q) -> Option<(K, V)> {
+        let index = self.search(key).ok()?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Returns an iterator over the entries with keys within `range`, sorted by key.
+    ///
+    /// # Panics
+    ///
+    /// If the start of the range is greater than the end, or if they are equal and both excluded.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        Q: Comparable<K> + ArbitraryOrd + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Iter::new(entries::range(&self.entries, entries::key, range))
+    }
+
+    /// Returns the entry for `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let search = self.search(&key);
+        entries::entry(&mut self.entries, search, key)
+    }
+}
+
+impl<K, V> Default for OrderedVecMap<K, V> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for OrderedVecMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: ArbitraryOrd, Q: Comparable<K> + ?Sized, V> Index<&Q> for OrderedVecMap<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to `key`.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is not present in the map.
+    fn index(&self, key: &Q) -> &V { self.get(key).expect("no entry found for key") }
+}
+
+impl<K: ArbitraryOrd, V> FromIterator<(K, V)> for OrderedVecMap<K, V> {
+    /// Collects the entries and sorts them once, see [`OrderedVecMap::from_vec`].
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<K: ArbitraryOrd, V> Extend<(K, V)> for OrderedVecMap<K, V> {
+    /// Appends the entries and sorts the map once, with the same result as inserting them in
+    /// order.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+        entries::sort_dedup(&mut self.entries, K::arbitrary_cmp);
+    }
+}
+
+impl<K, V> IntoIterator for OrderedVecMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter { self.entries.into_iter() }
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderedVecMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut OrderedVecMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use core::ops::Bound;
+
+    use super::*;
+    use crate::entries::tests::{p, Point};
+
+    fn map() -> OrderedVecMap<Point, u32> {
+        [(p(3, 0), 0), (p(1, 1), 1), (p(2, 5), 2), (p(0, 9), 3)].into_iter().collect()
+    }
+
+    #[test]
+    fn from_iter_sorts_and_keeps_last_value() {
+        let map: OrderedVecMap<_, _> =
+            [(p(2, 0), 'a'), (p(1, 0), 'b'), (p(2, 0), 'c'), (p(0, 0), 'd'), (p(2, 0), 'e')]
+                .into_iter()
+                .collect();
+        assert_eq!(map.as_slice(), [(p(0, 0), 'd'), (p(1, 0), 'b'), (p(2, 0), 'e')]);
+    }
+
+    #[test]
+    fn extend_replaces_values() {
+        let mut map = map();
+        map.extend([(p(1, 1), 10), (p(4, 0), 4), (p(1, 1), 11)]);
+
+        assert_eq!(map.get(&p(1, 1)), Some(&11));
+        assert_eq!(
+            map.keys().copied().collect::<Vec<_>>(),
+            [p(0, 9), p(1, 1), p(2, 5), p(3, 0), p(4, 0)]
+        );
+    }
+
+    #[test]
+    fn lookup() {
+        let map = map();
+
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get(&p(1, 1)), Some(&1));
+        assert_eq!(map.get(&p(1, 2)), None);
+        assert_eq!(map.get_key_value(&p(2, 5)), Some((&p(2, 5), &2)));
+        assert!(map.contains_key(&p(0, 9)));
+        assert_eq!(map[&p(3, 0)], 0);
+        assert_eq!(map.first_key_value(), Some((&p(0, 9), &3)));
+        assert_eq!(map.last_key_value(), Some((&p(3, 0), &0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "no entry found for key")]
+    fn index_panics() { let _ = map()[&p(9, 9)]; }
+
+    #[test]
+    fn insert_remove() {
+        let mut map = map();
+
+        assert_eq!(map.insert(p(1, 1), 10), Some(1));
+        assert_eq!(map.insert(p(1, 0), 11), None);
+        assert_eq!(
+            map.keys().copied().collect::<Vec<_>>(),
+            [p(0, 9), p(1, 0), p(1, 1), p(2, 5), p(3, 0)]
+        );
+
+        *map.get_mut(&p(1, 0)).unwrap() += 1;
+        assert_eq!(map.remove(&p(1, 0)), Some(12));
+        assert_eq!(map.remove(&p(1, 0)), None);
+        assert_eq!(map.remove_entry(&p(2, 5)), Some((p(2, 5), 2)));
+        assert_eq!(map.pop_first(), Some((p(0, 9), 3)));
+        assert_eq!(map.pop_last(), Some((p(3, 0), 0)));
+        assert_eq!(map.into_vec(), [(p(1, 1), 10)]);
+    }
+
+    #[test]
+    fn range() {
+        let map = map();
+
+        let range = map.range(p(1, 0)..p(3, 0)).collect::<Vec<_>>();
+        assert_eq!(range, [(&p(1, 1), &1), (&p(2, 5), &2)]);
+        assert_eq!(map.range(p(1, 1)..=p(3, 0)).count(), 3);
+        assert_eq!(map.range::<Point, _>(..&p(1, 1)).count(), 1);
+        assert_eq!(map.range::<Point, _>((Bound::Excluded(&p(2, 5)), Bound::Unbounded)).count(), 1);
+        assert_eq!(map.range(p(4, 0)..).count(), 0);
+        assert_eq!(map.range(p(1, 2)..p(1, 3)).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn reversed_range() { let _ = map().range(p(1, 3)..p(1, 2)); }
+
+    #[test]
+    fn entry() {
+        let mut map = map();
+
+        *map.entry(p(1, 1)).or_insert(0) += 10;
+        *map.entry(p(5, 5)).or_default() += 1;
+        map.entry(p(0, 9)).and_modify(|v| *v = 30).or_insert(0);
+        assert_eq!(map.entry(p(6, 6)).key(), &p(6, 6));
+
+        match map.entry(p(3, 0)) {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.insert(7), 0);
+                assert_eq!(entry.remove_entry(), (p(3, 0), 7));
+            }
+            Entry::Vacant(_) => panic!("entry should be occupied"),
+        }
+        match map.entry(p(4, 4)) {
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), p(4, 4)),
+            Entry::Occupied(_) => panic!("entry should be vacant"),
+        }
+
+        let values = map.values().copied().collect::<Vec<_>>();
+        assert_eq!(values, [30, 11, 2, 1]);
+    }
+
+    #[test]
+    fn iteration() {
+        let mut map = map();
+
+        for (_, v) in &mut map {
+            *v *= 2;
+        }
+        map.values_mut().for_each(|v| *v += 1);
+        map.retain(|k, _| k.x != 2);
+
+        assert_eq!(map.iter().len(), 3);
+        assert_eq!(map.iter().next_back(), Some((&p(3, 0), &1)));
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), [(p(0, 9), 7), (p(1, 1), 3), (p(3, 0), 1)]);
+    }
+
+    #[test]
+    fn unsized_lookup() {
+        let map: OrderedVecMap<_, _> =
+            [(String::from("b"), 2), (String::from("a"), 1)].into_iter().collect();
+
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map["b"], 2);
+        assert_eq!(map.range::<str, _>((Bound::Excluded("a"), Bound::Unbounded)).count(), 1);
+    }
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Tests the `arbitrary` feature.
+
+use core::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use arbitrary::{Arbitrary, Unstructured};
+use ordered::{laws, ArbitraryOrd, CheckedOrdered, Ordered};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Arbitrary)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+impl ArbitraryOrd for Point {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (self.x, self.y).cmp(&(other.x, other.y)) }
+}
+
+/// Ignores `y`, so is inconsistent with `PartialEq`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Arbitrary)]
+struct IgnoresY(Point);
+
+impl ArbitraryOrd for IgnoresY {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.x.cmp(&other.0.x) }
+}
+
+/// Derives `Arbitrary` even though it contains `Ordered` fields.
+#[derive(Debug, Arbitrary)]
+struct Index {
+    first: Ordered<Point>,
+    map: BTreeMap<Ordered<Point>, u32>,
+}
+
+const DATA: [u8; 16] = [5, 7, 2, 3, 1, 0, 0, 0, 9, 9, 4, 0, 0, 0, 1, 2];
+
+#[test]
+fn ordered_is_generated_like_inner() {
+    let point = Point::arbitrary(&mut Unstructured::new(&DATA)).unwrap();
+    let ordered = Ordered::<Point>::arbitrary(&mut Unstructured::new(&DATA)).unwrap();
+
+    assert_eq!(ordered, Ordered(point));
+    assert_eq!(Ordered::<Point>::size_hint(0), Point::size_hint(0));
+
+    let checked = CheckedOrdered::<Point>::arbitrary(&mut Unstructured::new(&DATA)).unwrap();
+    assert_eq!(checked, CheckedOrdered(point));
+}
+
+#[test]
+fn can_derive_with_ordered_fields() {
+    let index = Index::arbitrary_take_rest(Unstructured::new(&DATA)).unwrap();
+
+    assert_eq!(index.first, Ordered(Point { x: 5, y: 7 }));
+    assert!(index.map.keys().zip(index.map.keys().skip(1)).all(|(a, b)| a < b));
+}
+
+#[test]
+fn fuzz_helper_checks_laws() {
+    for chunk in DATA.chunks(6) {
+        assert_eq!(laws::fuzz_with_hash::<Point>(&mut Unstructured::new(chunk)), Ok(()));
+        assert_eq!(laws::fuzz_with_hash::<Option<Point>>(&mut Unstructured::new(chunk)), Ok(()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "`ArbitraryOrd` violates consistency with `PartialEq`")]
+fn fuzz_helper_reports_violation() {
+    // The first two points have the same `x` but a different `y`.
+    let data = [1, 2, 1, 3, 0, 0];
+    let _ = laws::fuzz_with_hash::<IgnoresY>(&mut Unstructured::new(&data));
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Tests installing a hook for `CheckedOrdered`, in its own process because the hook is global.
+
+use core::cmp::Ordering;
+use std::sync::atomic::{self, AtomicUsize};
+
+use ordered::checked::{self, Violation, ViolationKind};
+use ordered::{ArbitraryOrd, CheckedOrdered};
+
+/// Compares case insensitively but `PartialEq` is case sensitive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Letter(u8);
+
+impl ArbitraryOrd for Letter {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering {
+        self.0.to_ascii_lowercase().cmp(&other.0.to_ascii_lowercase())
+    }
+}
+
+static VIOLATIONS: AtomicUsize = AtomicUsize::new(0);
+static WITH_OPERANDS: AtomicUsize = AtomicUsize::new(0);
+
+fn count(violation: &Violation) {
+    assert_eq!(violation.kind, ViolationKind::InconsistentWithEq { eq: false });
+    assert!(violation.type_name.ends_with("Letter"));
+    assert_eq!(violation.location.file(), file!());
+    if let (Some(a), Some(b)) = (violation.a, violation.b) {
+        assert_eq!(format!("{:?} {:?}", a, b), "Letter(97) Letter(65)");
+        WITH_OPERANDS.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+    VIOLATIONS.fetch_add(1, atomic::Ordering::Relaxed);
+}
+
+#[test]
+fn hook_is_called_instead_of_panicking() {
+    checked::set_hook(count);
+
+    let a = CheckedOrdered(Letter(b'a'));
+    let b = CheckedOrdered(Letter(b'A'));
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert_eq!(a.cmp(&a), Ordering::Equal);
+    // The operators report the location of the caller too.
+    assert!(a <= b);
+    assert!(a >= b);
+    assert_eq!(a.debug_cmp(&b), Ordering::Equal);
+
+    let debug = usize::from(cfg!(debug_assertions));
+    assert_eq!(VIOLATIONS.load(atomic::Ordering::Relaxed), 4 * debug);
+    assert_eq!(WITH_OPERANDS.load(atomic::Ordering::Relaxed), debug);
+
+    checked::reset_hook();
+    if cfg!(debug_assertions) {
+        assert!(std::panic::catch_unwind(|| a.cmp(&b)).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Tests for `#[derive(ArbitraryOrd)]`.
+
+use core::cmp::Ordering;
+
+use ordered::{ArbitraryOrd, Ordered};
+
+/// A type that implements `ArbitraryOrd` but not `Ord`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+impl ArbitraryOrd for Point {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (self.x, self.y).cmp(&(other.x, other.y)) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+struct Named {
+    point: Point,
+    name: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+struct Tuple(u32, Point);
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+struct Unit;
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+struct Attributes {
+    #[ordered(reverse)]
+    a: u32,
+    #[ordered(by = by_len)]
+    b: &'static str,
+    #[ordered(skip)]
+    c: u32,
+}
+
+fn by_len(a: &&str, b: &&str) -> Ordering { a.len().cmp(&b.len()) }
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+enum Foo {
+    Space(Point),
+    Time { t: u32 },
+    Nothing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+enum Ranked {
+    #[ordered(rank = 10)]
+    A,
+    B(#[ordered(reverse)] u32),
+    #[ordered(rank = 5)]
+    C,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+struct Generic<T> {
+    inner: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+#[ordered(bound = "T: Ord")]
+struct Bounded<T> {
+    inner: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ArbitraryOrd)]
+enum Empty {}
+
+#[test]
+fn named_struct_is_lexicographic() {
+    let a = Named { point: Point { x: 1, y: 2 }, name: "z" };
+    let b = Named { point: Point { x: 1, y: 3 }, name: "a" };
+    let c = Named { point: Point { x: 1, y: 3 }, name: "b" };
+
+    assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+    assert_eq!(b.arbitrary_cmp(&c), Ordering::Less);
+    assert_eq!(c.arbitrary_cmp(&a), Ordering::Greater);
+    assert_eq!(c.arbitrary_cmp(&c.clone()), Ordering::Equal);
+}
+
+#[test]
+fn tuple_struct_is_lexicographic() {
+    let a = Tuple(1, Point { x: 9, y: 9 });
+    let b = Tuple(2, Point { x: 0, y: 0 });
+    let c = Tuple(2, Point { x: 0, y: 1 });
+
+    assert!(Ordered(&a) < Ordered(&b));
+    assert!(Ordered(&b) < Ordered(&c));
+}
+
+#[test]
+fn unit_struct_is_equal() {
+    assert_eq!(Unit.arbitrary_cmp(&Unit), Ordering::Equal);
+}
+
+#[test]
+fn field_attributes() {
+    let a = Attributes { a: 2, b: "zz", c: 0 };
+    let b = Attributes { a: 1, b: "a", c: 0 };
+    let c = Attributes { a: 1, b: "aaa", c: 0 };
+    let d = Attributes { a: 1, b: "aaa", c: 1 };
+
+    assert_eq!(a.arbitrary_cmp(&b), Ordering::Less); // Reversed.
+    assert_eq!(b.arbitrary_cmp(&c), Ordering::Less); // By length.
+    assert_eq!(c.arbitrary_cmp(&d), Ordering::Equal); // Skipped.
+}
+
+#[test]
+fn enum_orders_by_declaration_then_fields() {
+    let a = Foo::Space(Point { x: 5, y: 5 });
+    let b = Foo::Space(Point { x: 6, y: 0 });
+    let c = Foo::Time { t: 0 };
+    let d = Foo::Nothing;
+
+    assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+    assert_eq!(b.arbitrary_cmp(&c), Ordering::Less);
+    assert_eq!(c.arbitrary_cmp(&d), Ordering::Less);
+    assert_eq!(d.arbitrary_cmp(&a), Ordering::Greater);
+    assert_eq!(c.arbitrary_cmp(&Foo::Time { t: 0 }), Ordering::Equal);
+}
+
+#[test]
+fn enum_explicit_rank() {
+    // Ranks are C = 5, A = 10, B = 11.
+    assert_eq!(Ranked::C.arbitrary_cmp(&Ranked::A), Ordering::Less);
+    assert_eq!(Ranked::A.arbitrary_cmp(&Ranked::B(0)), Ordering::Less);
+    assert_eq!(Ranked::B(2).arbitrary_cmp(&Ranked::B(1)), Ordering::Less);
+}
+
+#[test]
+fn generic_uses_arbitrary_ord_bound() {
+    let a = Generic { inner: Point { x: 0, y: 1 } };
+    let b = Generic { inner: Point { x: 1, y: 0 } };
+
+    assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+}
+
+#[test]
+fn generic_with_explicit_bound() {
+    let a = Bounded { inner: 1_u32 };
+    let b = Bounded { inner: 2_u32 };
+
+    assert_eq!(a.arbitrary_cmp(&b), Ordering::Less);
+}
+
+#[test]
+fn empty_enum_compiles() {
+    fn assert_arbitrary_ord<T: ArbitraryOrd>() {}
+    assert_arbitrary_ord::<Empty>();
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Tests the law-checking harness against the `ArbitraryOrd` impls in this crate.
+
+use core::cmp::Ordering;
+
+use ordered::float::{Canonical, Float, NanFirst, NanLast, TotalOrder};
+use ordered::{arbitrary_ord_laws, ArbitraryOrd};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+impl ArbitraryOrd for Point {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (self.x, self.y).cmp(&(other.x, other.y)) }
+}
+
+fn point() -> impl Strategy<Value = Point> {
+    // Small coordinates so that equal points are actually generated.
+    (0_u32..4, 0_u32..4).prop_map(|(x, y)| Point { x, y })
+}
+
+fn float() -> impl Strategy<Value = f64> {
+    prop_oneof![any::<f64>(), Just(0.0), Just(-0.0), Just(f64::NAN), Just(-f64::NAN)]
+}
+
+arbitrary_ord_laws!(point_laws, point(), hash);
+arbitrary_ord_laws!(tuple_laws, (point(), any::<u8>()), hash);
+arbitrary_ord_laws!(option_laws, proptest::option::of(point()), hash);
+arbitrary_ord_laws!(vec_laws, proptest::collection::vec(point(), 0..3), hash);
+arbitrary_ord_laws!(total_order_laws, float().prop_map(Float::<f64, TotalOrder>::new), hash);
+arbitrary_ord_laws!(nan_first_laws, float().prop_map(Float::<f64, NanFirst>::new), hash);
+arbitrary_ord_laws!(nan_last_laws, float().prop_map(Float::<f64, NanLast>::new), hash);
+arbitrary_ord_laws!(canonical_laws, float().prop_map(Float::<f64, Canonical>::new), hash);
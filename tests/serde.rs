@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Tests for the `serde` implementations on `Ordered` and `CheckedOrdered`.
+
+use core::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use ordered::{ArbitraryOrd, CheckedOrdered, Ordered};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+impl ArbitraryOrd for Point {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { (self.x, self.y).cmp(&(other.x, other.y)) }
+}
+
+/// A type that serializes as a string so it can be used as a JSON object key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+struct Name(String);
+
+impl ArbitraryOrd for Name {
+    fn arbitrary_cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Adt {
+    name: String,
+    point: Ordered<Point>,
+}
+
+#[test]
+fn json_serializes_like_inner() {
+    let point = Point { x: 1, y: 2 };
+
+    let want = serde_json::to_string(&point).unwrap();
+    let got = serde_json::to_string(&Ordered(point)).unwrap();
+
+    assert_eq!(got, want);
+}
+
+#[test]
+fn json_checked_roundtrip() {
+    let point = CheckedOrdered(Point { x: 1, y: 2 });
+
+    let ser = serde_json::to_string(&point).unwrap();
+    assert_eq!(ser, r#"{"x":1,"y":2}"#);
+
+    let got: CheckedOrdered<Point> = serde_json::from_str(&ser).unwrap();
+    assert_eq!(got, point);
+}
+
+#[test]
+fn json_roundtrip() {
+    let adt = Adt { name: "foo".to_owned(), point: Ordered(Point { x: 1, y: 2 }) };
+
+    let ser = serde_json::to_string(&adt).unwrap();
+    assert_eq!(ser, r#"{"name":"foo","point":{"x":1,"y":2}}"#);
+
+    let got: Adt = serde_json::from_str(&ser).unwrap();
+    assert_eq!(got, adt);
+}
+
+#[test]
+fn json_map_keys() {
+    let mut map = BTreeMap::new();
+    map.insert(Ordered(Name("b".to_owned())), 2);
+    map.insert(Ordered(Name("a".to_owned())), 1);
+
+    let ser = serde_json::to_string(&map).unwrap();
+    assert_eq!(ser, r#"{"a":1,"b":2}"#);
+
+    let got: BTreeMap<Ordered<Name>, u32> = serde_json::from_str(&ser).unwrap();
+    assert_eq!(got, map);
+}
+
+#[test]
+fn bincode_serializes_like_inner() {
+    let point = Point { x: 1, y: 2 };
+
+    let want = bincode::serialize(&point).unwrap();
+    let got = bincode::serialize(&Ordered(point)).unwrap();
+
+    assert_eq!(got, want);
+}
+
+#[test]
+fn bincode_roundtrip() {
+    let mut map = BTreeMap::new();
+    map.insert(Ordered(Point { x: 1, y: 2 }), "a".to_owned());
+    map.insert(Ordered(Point { x: 0, y: 5 }), "b".to_owned());
+
+    let ser = bincode::serialize(&map).unwrap();
+    let got: BTreeMap<Ordered<Point>, String> = bincode::deserialize(&ser).unwrap();
+
+    assert_eq!(got, map);
+}